@@ -0,0 +1,41 @@
+#import bevy_sprite::mesh2d_view_bindings globals, view
+#import bevy_sprite::mesh2d_vertex_output MeshVertexOutput
+#import yt_raymarch_2d::sdf center_sdf, shapes, sd_circle, smin, draw_contours
+
+// The playground loads this file when no `shader=` parameter
+// is given. Copy it somewhere, point `shader=` at the copy (or
+// drop the file onto the window) and edit away: the uniform
+// block below is the one the playground material always binds.
+struct SdfDemoMaterial {
+    color: vec4<f32>,
+    shape: u32,
+    mouse: vec2<f32>,
+    contours: vec4<f32>
+};
+
+@group(1) @binding(0)
+var<uniform> material: SdfDemoMaterial;
+
+@fragment
+fn fragment(
+    mesh: MeshVertexOutput,
+) -> @location(0) vec4<f32> {
+    let coord = center_sdf(mesh.uv, view.viewport.zw);
+    let mouse = center_sdf(material.mouse, view.viewport.zw);
+
+    let wobble = sin(globals.time) * 0.1;
+    let distance = smin(
+        shapes(material.shape, coord),
+        sd_circle(coord, mouse, 0.1 + wobble),
+        0.2
+    );
+    let width = fwidth(distance);
+
+    if (distance >= 0.) {
+        let color = mix(vec4(0.,0.,0.,1.), vec4(1.,0.647,0., 1.), distance);
+        return draw_contours(color, distance, width, material.contours);
+    } else {
+        let color = mix(vec4(0.,0.,0.,1.), vec4(0.,0.855,1.,1.), abs(distance));
+        return draw_contours(color, distance, width, material.contours);
+    }
+}
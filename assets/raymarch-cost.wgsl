@@ -0,0 +1,101 @@
+#import bevy_sprite::mesh2d_view_bindings view
+#import bevy_sprite::mesh2d_vertex_output MeshVertexOutput
+#import yt_raymarch_2d::sdf center_sdf, shapes, color_ramp, draw_contours
+
+struct SdfDemoMaterial {
+    shape: u32,
+    mouse: vec2<f32>,
+    strategy: u32,
+    relaxation: f32,
+    projection: u32,
+    angle: f32,
+    color_by: u32,
+    max_steps: f32,
+    contours: vec4<f32>
+};
+
+@group(1) @binding(0)
+var<uniform> material: SdfDemoMaterial;
+
+// the rest of `RayMarcher::default()` in raymarch.rs
+const MAX_DISTANCE: f32 = 4.0;
+const EPSILON: f32 = 0.001;
+
+struct March {
+    steps: u32,
+    // 0 hit, 1 escaped, 2 out of steps
+    termination: u32,
+};
+
+// march counts the steps from `origin` along `direction`,
+// like `RayMarcher::march`. Strategy 1 over-relaxes the
+// steps, falling back to plain sphere tracing when the
+// circles of two points in a row don't overlap.
+fn march(origin: vec2f, direction: vec2f) -> March {
+    var omega = select(1.0, material.relaxation, material.strategy == 1u);
+    var t = 0.0;
+    var previous_radius = 0.0;
+    var step = 0.0;
+    let max_steps = u32(material.max_steps);
+    for (var i = 0u; i < max_steps; i++) {
+        let signed_radius = shapes(material.shape, origin + direction * t);
+        let radius = abs(signed_radius);
+        let overshot = omega > 1.0 && radius + previous_radius < step;
+        if overshot {
+            step -= omega * step;
+            omega = 1.0;
+        } else {
+            step = signed_radius * omega;
+        }
+        previous_radius = radius;
+        if !overshot {
+            if signed_radius < EPSILON {
+                return March(i + 1u, 0u);
+            }
+            if t > MAX_DISTANCE {
+                return March(i + 1u, 1u);
+            }
+        }
+        t += step;
+    }
+    return March(max_steps, 2u);
+}
+
+@fragment
+fn fragment(
+    mesh: MeshVertexOutput,
+) -> @location(0) vec4<f32> {
+    let coord = center_sdf(mesh.uv, view.viewport.zw);
+
+    let distance = shapes(material.shape, coord);
+    let width = fwidth(distance);
+
+    var origin = center_sdf(material.mouse, view.viewport.zw);
+    let offset = coord - origin;
+    var direction = select(vec2(0.0), normalize(offset), length(offset) > 0.0);
+    if material.projection == 1u {
+        // the scene's y points down
+        let angle = radians(material.angle);
+        origin = coord;
+        direction = vec2(cos(angle), -sin(angle));
+    }
+
+    let result = march(origin, direction);
+    let cost = f32(result.steps) / max(material.max_steps, 1.0);
+
+    var rgb = color_ramp(2u, cost);
+    if material.color_by == 1u {
+        var termination = vec3(0.9, 0.1, 0.1);
+        if result.termination == 0u {
+            termination = vec3(0.1, 0.8, 0.2);
+        } else if result.termination == 1u {
+            termination = vec3(0.1, 0.3, 0.9);
+        }
+        rgb = termination * (0.35 + 0.65 * cost);
+    }
+
+    // outline the surface
+    let outline = clamp(1.5 - abs(distance) / max(width, 0.000001), 0.0, 1.0);
+    let color = vec4(mix(rgb, vec3(1.0), outline), 1.0);
+    return draw_contours(color, distance, width, material.contours);
+}
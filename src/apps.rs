@@ -5,3 +5,7 @@ pub mod single_sdf_distance_as_gradient_with_abs_mouse;
 pub mod single_sdf_distance_as_gradient_with_algorithm;
 pub mod single_sdf_distance_as_gradient_with_mouse;
 pub mod single_sdf_distance_as_gradient_with_mouse_and_inner_ray;
+pub mod playground;
+pub mod compare_shapes;
+pub mod compare_circle_and_border;
+pub mod raymarch_cost;
@@ -0,0 +1,62 @@
+use crate::{
+    apps::{
+        single_sdf_distance_as_border,
+        single_sdf_distance_as_circle::{self, Lights},
+    },
+    bevy_plugin_shader2d::{
+        run_example, CompareLayout, Shader2dComparePlugin,
+    },
+};
+use bevy::prelude::*;
+
+pub const ID: &str = "compare-circle-and-border";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompareCircleAndBorderConfig {
+    pub lights: Lights,
+    /// The border's thickness.
+    pub thickness: f32,
+    pub layout: CompareLayout,
+}
+
+impl Default for CompareCircleAndBorderConfig {
+    fn default() -> Self {
+        Self {
+            lights: Lights::default(),
+            thickness: 0.005,
+            layout: CompareLayout::default(),
+        }
+    }
+}
+
+pub fn app(config: CompareCircleAndBorderConfig) {
+    run_example(
+        ID,
+        CompareCircleAndBorderPlugin { config },
+    );
+}
+
+/// The circle example on the left and the border example
+/// on the right, both following the same mouse: the
+/// circle's edge and the border are the same distance
+/// from the scene.
+#[derive(Default)]
+pub struct CompareCircleAndBorderPlugin {
+    pub config: CompareCircleAndBorderConfig,
+}
+
+impl Plugin for CompareCircleAndBorderPlugin {
+    fn build(&self, app: &mut App) {
+        let config = self.config;
+        app.add_plugins(Shader2dComparePlugin {
+            example: ID,
+            left: single_sdf_distance_as_circle::SdfDemoMaterial::new(
+                config.lights,
+            ),
+            right: single_sdf_distance_as_border::SdfDemoMaterial::new(
+                config.thickness,
+            ),
+            layout: config.layout,
+        });
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    apps::single_sdf_distance_as_gradient::SdfDemoMaterial,
+    bevy_plugin_shader2d::{
+        run_example, CompareLayout, Shader2dComparePlugin,
+    },
+    gradient::GradientColors,
+    sdf::Shape,
+};
+use bevy::prelude::*;
+
+pub const ID: &str = "compare-shapes";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompareShapesConfig {
+    pub left: Shape,
+    pub right: Shape,
+    pub colors: GradientColors,
+    pub layout: CompareLayout,
+}
+
+impl Default for CompareShapesConfig {
+    fn default() -> Self {
+        Self {
+            left: Shape::Circle,
+            right: Shape::CoolS,
+            colors: GradientColors::default(),
+            layout: CompareLayout::default(),
+        }
+    }
+}
+
+pub fn app(config: CompareShapesConfig) {
+    run_example(ID, CompareShapesPlugin { config });
+}
+
+/// The distance gradients of two shapes, next to each
+/// other.
+#[derive(Default)]
+pub struct CompareShapesPlugin {
+    pub config: CompareShapesConfig,
+}
+
+impl Plugin for CompareShapesPlugin {
+    fn build(&self, app: &mut App) {
+        let config = self.config;
+        app.add_plugins(Shader2dComparePlugin {
+            example: ID,
+            left: SdfDemoMaterial::new(
+                config.colors,
+                config.left,
+            ),
+            right: SdfDemoMaterial::new(
+                config.colors,
+                config.right,
+            ),
+            layout: config.layout,
+        });
+    }
+}
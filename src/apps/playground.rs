@@ -0,0 +1,439 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    bevy_plugin_shader2d::{
+        run_example, SdfMouse, Shader2dWindowPlugin,
+    },
+    switcher::{ActiveExample, ExampleEntity},
+};
+use bevy::{
+    asset::{AssetPath, HandleId, LoadState},
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_resource::{
+            ExtractResource, ExtractResourcePlugin,
+        },
+        mesh::MeshVertexBufferLayout,
+        render_resource::{
+            AsBindGroup, CachedPipelineState,
+            PipelineCache, PipelineCacheError,
+            PipelineDescriptor, RenderPipelineDescriptor,
+            ShaderRef, SpecializedMeshPipelineError,
+        },
+        Render, RenderApp, RenderSet,
+    },
+    sprite::{Material2d, Material2dKey},
+    window::FileDragAndDrop,
+};
+
+/// The shader loaded when no other is configured.
+const DEFAULT_SHADER: &str = "playground.wgsl";
+
+pub const ID: &str = "playground";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaygroundConfig {
+    /// An asset path, relative to `assets/` (or absolute on
+    /// native).
+    pub shader: String,
+}
+
+impl Default for PlaygroundConfig {
+    fn default() -> Self {
+        Self {
+            shader: DEFAULT_SHADER.to_string(),
+        }
+    }
+}
+
+pub fn app(config: PlaygroundConfig) {
+    run_example(ID, PlaygroundPlugin { config });
+}
+
+/// Runs a fragment shader picked at runtime against the
+/// standard `SdfDemoMaterial` uniform block.
+///
+/// Dropping a `.wgsl` file onto the window swaps it out.
+/// Files loaded through the asset server hot-reload as
+/// they're saved.
+#[derive(Default)]
+pub struct PlaygroundPlugin {
+    pub config: PlaygroundConfig,
+}
+
+impl Plugin for PlaygroundPlugin {
+    fn build(&self, app: &mut App) {
+        let name = self.config.shader.clone();
+
+        app.add_plugins((Shader2dWindowPlugin {
+            example: ID,
+            shader: SdfDemoMaterial {
+                color: Color::BLUE,
+                shape: 1,
+                mouse: Vec2::splat(0.),
+                contours: Vec4::ZERO,
+                // path based ids match the handle the asset
+                // server hands out below
+                shader: Handle::weak(HandleId::from(
+                    AssetPath::from(name.as_str()),
+                )),
+            },
+        },));
+
+        let handle = app
+            .world
+            .resource::<AssetServer>()
+            .load(name.as_str());
+        let errors = ShaderErrors::default();
+        let active = in_state(ActiveExample(ID));
+
+        app.insert_resource(PlaygroundShader {
+            handle,
+            name,
+        })
+        .insert_resource(errors.clone())
+        .add_plugins(ExtractResourcePlugin::<
+            PlaygroundShader,
+        >::default())
+        .add_systems(OnEnter(ActiveExample(ID)), setup)
+        .add_systems(
+            Update,
+            (
+                update_mouse,
+                load_dropped_shader,
+                update_material_shader,
+                show_shader_status,
+            )
+                .run_if(active.clone()),
+        );
+
+        #[cfg(target_arch = "wasm32")]
+        app.init_resource::<WebDroppedShaders>()
+            .add_systems(Startup, listen_for_web_drops)
+            .add_systems(
+                Update,
+                load_web_dropped_shader
+                    .before(update_material_shader)
+                    .run_if(active),
+            );
+
+        if let Ok(render_app) =
+            app.get_sub_app_mut(RenderApp)
+        {
+            render_app.insert_resource(errors).add_systems(
+                Render,
+                report_shader_errors
+                    .in_set(RenderSet::Cleanup),
+            );
+        }
+    }
+}
+
+/// The shader the playground material currently renders
+/// with.
+#[derive(Resource, ExtractResource, Clone)]
+struct PlaygroundShader {
+    handle: Handle<Shader>,
+    name: String,
+}
+
+/// The latest compile error for the playground shader,
+/// written by the render world and read by the UI.
+#[derive(Resource, Clone, Default)]
+struct ShaderErrors(Arc<Mutex<Option<String>>>);
+
+#[derive(Component)]
+struct ShaderStatus;
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 18.0,
+                color: Color::rgb(1.0, 0.4, 0.4),
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            left: Val::Px(20.),
+            bottom: Val::Px(20.),
+            right: Val::Px(20.),
+            ..default()
+        }),
+        ShaderStatus,
+        ExampleEntity,
+    ));
+}
+
+fn update_mouse(
+    mouse: Res<SdfMouse>,
+    mut materials: ResMut<Assets<SdfDemoMaterial>>,
+) {
+    if !mouse.is_changed() {
+        return;
+    };
+    for (_handle, mat) in materials.iter_mut() {
+        mat.mouse = mouse.position;
+    }
+}
+
+fn load_dropped_shader(
+    mut drops: EventReader<FileDragAndDrop>,
+    asset_server: Res<AssetServer>,
+    mut shader: ResMut<PlaygroundShader>,
+) {
+    for event in drops.iter() {
+        if let FileDragAndDrop::DroppedFile {
+            path_buf,
+            ..
+        } = event
+        {
+            shader.handle =
+                asset_server.load(path_buf.clone());
+            shader.name = path_buf.display().to_string();
+        }
+    }
+}
+
+/// Also catches the material created each time the
+/// playground starts, which begins with the default shader.
+fn update_material_shader(
+    shader: Res<PlaygroundShader>,
+    mut materials: ResMut<Assets<SdfDemoMaterial>>,
+) {
+    let stale = materials
+        .iter()
+        .filter(|(_, mat)| mat.shader != shader.handle)
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    for id in stale {
+        if let Some(mat) =
+            materials.get_mut(&Handle::weak(id))
+        {
+            mat.shader = shader.handle.clone();
+        }
+    }
+}
+
+fn show_shader_status(
+    shader: Res<PlaygroundShader>,
+    asset_server: Res<AssetServer>,
+    errors: Res<ShaderErrors>,
+    mut text: Query<&mut Text, With<ShaderStatus>>,
+) {
+    let status = if asset_server
+        .get_load_state(&shader.handle)
+        == LoadState::Failed
+    {
+        format!("could not load {}", shader.name)
+    } else if let Some(error) =
+        errors.0.lock().unwrap().as_ref()
+    {
+        format!("{}:\n{error}", shader.name)
+    } else {
+        String::new()
+    };
+
+    for mut text in text.iter_mut() {
+        if text.sections[0].value != status {
+            text.sections[0].value = status.clone();
+        }
+    }
+}
+
+/// Render world system that picks the playground shader's
+/// pipeline out of the cache and records why it failed to
+/// compile, if it did.
+fn report_shader_errors(
+    pipeline_cache: Res<PipelineCache>,
+    shader: Option<Res<PlaygroundShader>>,
+    errors: Res<ShaderErrors>,
+) {
+    let Some(shader) = shader else {
+        return;
+    };
+    let mut error = None;
+    for pipeline in pipeline_cache.pipelines() {
+        let PipelineDescriptor::RenderPipelineDescriptor(
+            descriptor,
+        ) = &pipeline.descriptor
+        else {
+            continue;
+        };
+        let uses_shader =
+            descriptor.fragment.as_ref().is_some_and(
+                |fragment| fragment.shader == shader.handle,
+            );
+        if !uses_shader {
+            continue;
+        }
+        error = match &pipeline.state {
+            CachedPipelineState::Err(
+                PipelineCacheError::ProcessShaderError(err),
+            ) => Some(err.to_string()),
+            CachedPipelineState::Err(
+                PipelineCacheError::CreateShaderModule(
+                    description,
+                ),
+            ) => Some(description.clone()),
+            _ => None,
+        };
+    }
+    *errors.0.lock().unwrap() = error;
+}
+
+/// Files dropped onto the canvas, read by the browser and
+/// waiting to be turned into shaders.
+#[cfg(target_arch = "wasm32")]
+#[derive(Resource, Clone, Default)]
+struct WebDroppedShaders(Arc<Mutex<Vec<(String, String)>>>);
+
+/// winit doesn't report file drops on the web, so listen
+/// for them on the page and read the file contents with a
+/// `FileReader`.
+#[cfg(target_arch = "wasm32")]
+fn listen_for_web_drops(dropped: Res<WebDroppedShaders>) {
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{DragEvent, FileReader, ProgressEvent};
+
+    let window =
+        web_sys::window().expect("expect a window");
+
+    // the browser only fires `drop` if `dragover` is
+    // cancelled
+    let on_dragover = Closure::<dyn FnMut(DragEvent)>::new(
+        |event: DragEvent| event.prevent_default(),
+    );
+
+    let queue = dropped.0.clone();
+    let on_drop = Closure::<dyn FnMut(DragEvent)>::new(
+        move |event: DragEvent| {
+            event.prevent_default();
+            let Some(file) = event
+                .data_transfer()
+                .and_then(|transfer| transfer.files())
+                .and_then(|files| files.get(0))
+            else {
+                return;
+            };
+            let Ok(reader) = FileReader::new() else {
+                return;
+            };
+
+            let name = file.name();
+            let queue = queue.clone();
+            let result = reader.clone();
+            let on_load =
+                Closure::<dyn FnMut(ProgressEvent)>::new(
+                    move |_: ProgressEvent| {
+                        if let Some(source) =
+                            result.result().ok().and_then(
+                                |value| value.as_string(),
+                            )
+                        {
+                            queue.lock().unwrap().push((
+                                name.clone(),
+                                source,
+                            ));
+                        }
+                    },
+                );
+            reader.set_onload(Some(
+                on_load.as_ref().unchecked_ref(),
+            ));
+            on_load.forget();
+            reader
+                .read_as_text(&file)
+                .expect("failed to read dropped file");
+        },
+    );
+
+    window
+        .add_event_listener_with_callback(
+            "dragover",
+            on_dragover.as_ref().unchecked_ref(),
+        )
+        .expect("failed to listen for dragover");
+    window
+        .add_event_listener_with_callback(
+            "drop",
+            on_drop.as_ref().unchecked_ref(),
+        )
+        .expect("failed to listen for drop");
+    on_dragover.forget();
+    on_drop.forget();
+}
+
+/// Dropped files on the web aren't on any path the asset
+/// server can watch, so they're added as shaders directly.
+/// Drop the file again to pick up edits.
+#[cfg(target_arch = "wasm32")]
+fn load_web_dropped_shader(
+    dropped: Res<WebDroppedShaders>,
+    mut shaders: ResMut<Assets<Shader>>,
+    mut shader: ResMut<PlaygroundShader>,
+) {
+    for (name, source) in
+        dropped.0.lock().unwrap().drain(..)
+    {
+        shader.handle = shaders
+            .add(Shader::from_wgsl(source, name.clone()));
+        shader.name = name;
+    }
+}
+
+impl Material2d for SdfDemoMaterial {
+    fn fragment_shader() -> ShaderRef {
+        DEFAULT_SHADER.into()
+    }
+
+    fn specialize(
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout,
+        key: Material2dKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if let Some(fragment) = descriptor.fragment.as_mut()
+        {
+            fragment.shader = key.bind_group_data.shader;
+        }
+        Ok(())
+    }
+}
+
+#[derive(AsBindGroup, TypeUuid, Reflect, Debug, Clone)]
+#[uuid = "3aa64d6a-ea68-4e1c-ab0a-b27de713bb3d"]
+#[bind_group_data(SdfDemoMaterialKey)]
+pub struct SdfDemoMaterial {
+    #[uniform(0)]
+    color: Color,
+    #[uniform(0)]
+    shape: u32,
+    #[uniform(0)]
+    mouse: Vec2,
+    /// The [`Contours`](crate::contours::Contours) to
+    /// draw, if any.
+    #[uniform(0)]
+    contours: Vec4,
+    // not a uniform: picks the fragment shader in
+    // `specialize`
+    #[reflect(ignore)]
+    shader: Handle<Shader>,
+}
+
+/// Pipelines are specialized per shader, so switching the
+/// shader handle builds a new pipeline.
+#[derive(Eq, PartialEq, Hash, Clone)]
+pub struct SdfDemoMaterialKey {
+    shader: Handle<Shader>,
+}
+
+impl From<&SdfDemoMaterial> for SdfDemoMaterialKey {
+    fn from(material: &SdfDemoMaterial) -> Self {
+        Self {
+            shader: material.shader.clone(),
+        }
+    }
+}
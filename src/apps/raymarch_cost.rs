@@ -0,0 +1,157 @@
+use crate::{
+    bevy_plugin_shader2d::{
+        run_example, SdfMouse, Shader2dWindowPlugin,
+    },
+    gradient::{add_current_shape, CurrentShape},
+    params::Params,
+    raymarch::{RayMarcher, StepHeatmap},
+    sdf::Shape,
+    switcher::ActiveExample,
+};
+use bevy::{
+    prelude::*,
+    reflect::TypeUuid,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    sprite::Material2d,
+};
+
+pub const ID: &str = "raymarch-cost";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RaymarchCostConfig {
+    pub shape: Shape,
+    /// How the rays are marched and coloured. Radial rays
+    /// start at the mouse rather than
+    /// [`StepHeatmap::origin`].
+    pub heatmap: StepHeatmap,
+}
+
+impl Default for RaymarchCostConfig {
+    fn default() -> Self {
+        Self {
+            shape: Shape::Box,
+            heatmap: StepHeatmap::default(),
+        }
+    }
+}
+
+impl RaymarchCostConfig {
+    /// Reads the parameters `raymarch-cost` declares.
+    pub fn from_params(params: &Params) -> Self {
+        Self {
+            shape: params.get("shape"),
+            heatmap: StepHeatmap {
+                marcher: RayMarcher {
+                    strategy: params.get("strategy"),
+                    relaxation: params.get("relaxation"),
+                    max_steps: params.get("max_steps"),
+                    ..default()
+                },
+                projection: params.get("projection"),
+                angle: params.get("angle"),
+                color_by: params.get("color_by"),
+                ..default()
+            },
+        }
+    }
+}
+
+pub fn app(config: RaymarchCostConfig) {
+    run_example(ID, RaymarchCostPlugin { config });
+}
+
+#[derive(Default)]
+pub struct RaymarchCostPlugin {
+    pub config: RaymarchCostConfig,
+}
+
+impl Plugin for RaymarchCostPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((Shader2dWindowPlugin {
+            example: ID,
+            shader: SdfDemoMaterial::new(
+                self.config.shape,
+                self.config.heatmap,
+            ),
+        },))
+            .add_systems(
+                Update,
+                (update_mouse, update_shape)
+                    .run_if(in_state(ActiveExample(ID))),
+            );
+        add_current_shape(app, ID, self.config.shape);
+    }
+}
+
+fn update_shape(
+    mut materials: ResMut<Assets<SdfDemoMaterial>>,
+    current_shape: Res<CurrentShape>,
+) {
+    if current_shape.is_changed() {
+        for (_handle, mat) in materials.iter_mut() {
+            mat.shape = current_shape.as_u32()
+        }
+    }
+}
+
+fn update_mouse(
+    mouse: Res<SdfMouse>,
+    mut materials: ResMut<Assets<SdfDemoMaterial>>,
+) {
+    if !mouse.is_changed() {
+        return;
+    };
+    for (_handle, mat) in materials.iter_mut() {
+        mat.mouse = mouse.position;
+    }
+}
+
+impl SdfDemoMaterial {
+    pub fn new(shape: Shape, heatmap: StepHeatmap) -> Self {
+        Self {
+            shape: shape.as_u32(),
+            mouse: Vec2::splat(0.),
+            strategy: heatmap.marcher.strategy.as_u32(),
+            relaxation: heatmap.marcher.relaxation,
+            projection: heatmap.projection.as_u32(),
+            angle: heatmap.angle,
+            color_by: heatmap.color_by.as_u32(),
+            max_steps: heatmap.marcher.max_steps as f32,
+            contours: Vec4::ZERO,
+        }
+    }
+}
+
+impl Material2d for SdfDemoMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "raymarch-cost.wgsl".into()
+    }
+}
+
+#[derive(AsBindGroup, TypeUuid, Reflect, Debug, Clone)]
+#[uuid = "5f0f3b1e-7c2a-4d8e-9a61-3e4b2d7c9f10"]
+pub struct SdfDemoMaterial {
+    #[uniform(0)]
+    shape: u32,
+    #[uniform(0)]
+    mouse: Vec2,
+    /// A [`Strategy`](crate::raymarch::Strategy) id.
+    #[uniform(0)]
+    strategy: u32,
+    #[uniform(0)]
+    relaxation: f32,
+    /// A [`Projection`](crate::raymarch::Projection) id.
+    #[uniform(0)]
+    projection: u32,
+    #[uniform(0)]
+    angle: f32,
+    /// A [`ColorBy`](crate::raymarch::ColorBy) id.
+    #[uniform(0)]
+    color_by: u32,
+    #[uniform(0)]
+    max_steps: f32,
+    /// The [`Contours`](crate::contours::Contours) to
+    /// draw, if any.
+    #[uniform(0)]
+    contours: Vec4,
+}
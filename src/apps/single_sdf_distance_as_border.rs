@@ -1,38 +1,62 @@
-use crate::bevy_plugin_shader2d::Shader2dWindowPlugin;
+use crate::{
+    bevy_plugin_shader2d::{
+        run_example, SdfMouse, Shader2dWindowPlugin,
+    },
+    switcher::ActiveExample,
+};
 use bevy::{
     prelude::*,
-    reflect::{TypePath, TypeUuid},
+    reflect::TypeUuid,
     render::render_resource::{AsBindGroup, ShaderRef},
     sprite::Material2d,
 };
 
+pub const ID: &str = "single-sdf-distance-as-border";
+
 pub fn app() {
-    App::new()
-        .add_plugins((Shader2dWindowPlugin {
-            shader: SdfDemoMaterial {
-                color: Color::BLUE,
-                mouse: Vec2::splat(0.),
-            },
+    run_example(ID, SdfBorderPlugin);
+}
+
+/// Has nothing to configure: the border is always drawn at
+/// the mouse's distance. Its `thickness` is a material
+/// parameter, see
+/// [`MaterialParamsPlugin`](crate::material_params::MaterialParamsPlugin).
+#[derive(Default)]
+pub struct SdfBorderPlugin;
+
+impl Plugin for SdfBorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((Shader2dWindowPlugin {
+            example: ID,
+            shader: SdfDemoMaterial::new(0.005),
         },))
-        .add_systems(Update, (update_mouse,))
-        .run();
+            .add_systems(
+                Update,
+                (update_mouse,)
+                    .run_if(in_state(ActiveExample(ID))),
+            );
+    }
 }
 
 fn update_mouse(
-    window: Query<&Window, Changed<Window>>,
-    mut cursor_moved_events: EventReader<CursorMoved>,
+    mouse: Res<SdfMouse>,
     mut materials: ResMut<Assets<SdfDemoMaterial>>,
 ) {
-    if window.is_empty() {
+    if !mouse.is_changed() {
         return;
     };
-    let resolution = &window.single().resolution;
-    for event in cursor_moved_events.iter() {
-        for (_handle, mat) in materials.iter_mut() {
-            mat.mouse = Vec2::new(
-                event.position.x / resolution.width(),
-                event.position.y / resolution.height(),
-            );
+    for (_handle, mat) in materials.iter_mut() {
+        mat.mouse = mouse.position;
+    }
+}
+
+impl SdfDemoMaterial {
+    pub fn new(thickness: f32) -> Self {
+        Self {
+            color: Color::BLUE,
+            mouse: Vec2::splat(0.),
+            thickness,
+            contours: Vec4::ZERO,
         }
     }
 }
@@ -43,11 +67,19 @@ impl Material2d for SdfDemoMaterial {
     }
 }
 
-#[derive(AsBindGroup, TypeUuid, TypePath, Debug, Clone)]
-#[uuid = "f690fdae-d598-45ab-8225-97e2a3f056e0"]
+#[derive(AsBindGroup, TypeUuid, Reflect, Debug, Clone)]
+#[uuid = "393dd676-97dc-49f0-a0d6-386c59787c20"]
 pub struct SdfDemoMaterial {
     #[uniform(0)]
     color: Color,
     #[uniform(0)]
     mouse: Vec2,
+    /// How far either side of the mouse's distance the
+    /// border reaches.
+    #[uniform(0)]
+    thickness: f32,
+    /// The [`Contours`](crate::contours::Contours) to
+    /// draw, if any.
+    #[uniform(0)]
+    contours: Vec4,
 }
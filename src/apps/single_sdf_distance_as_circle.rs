@@ -1,46 +1,79 @@
-use crate::bevy_plugin_shader2d::Shader2dWindowPlugin;
+use crate::{
+    bevy_plugin_shader2d::{
+        run_example, SdfMouse, Shader2dWindowPlugin,
+    },
+    switcher::ActiveExample,
+};
 use bevy::{
     prelude::*,
-    reflect::{TypePath, TypeUuid},
+    reflect::TypeUuid,
     render::render_resource::{AsBindGroup, ShaderRef},
     sprite::Material2d,
 };
 
+pub const ID: &str = "single-sdf-distance-as-circle";
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum Lights {
+    #[default]
     On,
     Off,
 }
-pub fn app(lights: Lights) {
-    App::new()
-        .add_plugins((Shader2dWindowPlugin {
-            shader: SdfDemoMaterial {
-                color: Color::BLUE,
-                mouse: Vec2::splat(0.),
-                lights: match lights {
-                    Lights::On => 1.,
-                    Lights::Off => 0.,
-                },
-            },
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CircleConfig {
+    /// Whether the inside of the shape is lit (white) or
+    /// black.
+    pub lights: Lights,
+}
+
+pub fn app(config: CircleConfig) {
+    run_example(ID, SdfCirclePlugin { config });
+}
+
+#[derive(Default)]
+pub struct SdfCirclePlugin {
+    pub config: CircleConfig,
+}
+
+impl Plugin for SdfCirclePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((Shader2dWindowPlugin {
+            example: ID,
+            shader: SdfDemoMaterial::new(
+                self.config.lights,
+            ),
         },))
-        .add_systems(Update, (update_mouse,))
-        .run();
+            .add_systems(
+                Update,
+                (update_mouse,)
+                    .run_if(in_state(ActiveExample(ID))),
+            );
+    }
 }
 
 fn update_mouse(
-    window: Query<&Window, Changed<Window>>,
-    mut cursor_moved_events: EventReader<CursorMoved>,
+    mouse: Res<SdfMouse>,
     mut materials: ResMut<Assets<SdfDemoMaterial>>,
 ) {
-    if window.is_empty() {
+    if !mouse.is_changed() {
         return;
     };
-    let resolution = &window.single().resolution;
-    for event in cursor_moved_events.iter() {
-        for (_handle, mat) in materials.iter_mut() {
-            mat.mouse = Vec2::new(
-                event.position.x / resolution.width(),
-                event.position.y / resolution.height(),
-            );
+    for (_handle, mat) in materials.iter_mut() {
+        mat.mouse = mouse.position;
+    }
+}
+
+impl SdfDemoMaterial {
+    pub fn new(lights: Lights) -> Self {
+        Self {
+            color: Color::BLUE,
+            mouse: Vec2::splat(0.),
+            lights: match lights {
+                Lights::On => 1.,
+                Lights::Off => 0.,
+            },
+            contours: Vec4::ZERO,
         }
     }
 }
@@ -51,8 +84,8 @@ impl Material2d for SdfDemoMaterial {
     }
 }
 
-#[derive(AsBindGroup, TypeUuid, TypePath, Debug, Clone)]
-#[uuid = "f690fdae-d598-45ab-8225-97e2a3f056e0"]
+#[derive(AsBindGroup, TypeUuid, Reflect, Debug, Clone)]
+#[uuid = "3225f467-d2aa-4897-bc45-8fc6dccc4bc0"]
 pub struct SdfDemoMaterial {
     #[uniform(0)]
     color: Color,
@@ -60,4 +93,8 @@ pub struct SdfDemoMaterial {
     mouse: Vec2,
     #[uniform(0)]
     lights: f32,
+    /// The [`Contours`](crate::contours::Contours) to
+    /// draw, if any.
+    #[uniform(0)]
+    contours: Vec4,
 }
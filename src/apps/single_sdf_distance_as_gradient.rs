@@ -1,42 +1,136 @@
-use crate::bevy_plugin_shader2d::Shader2dWindowPlugin;
+use crate::{
+    bevy_plugin_shader2d::{
+        run_example, SdfMouse, Shader2dWindowPlugin,
+    },
+    gradient::{
+        add_current_shape, shape_for_key, CurrentShape,
+        GradientColors, GradientConfig,
+    },
+    medial_axis::add_medial_axis,
+    sdf::Shape,
+    switcher::ActiveExample,
+    vector_field::add_vector_field,
+};
 use bevy::{
     prelude::*,
-    reflect::{TypePath, TypeUuid},
+    reflect::TypeUuid,
     render::render_resource::{AsBindGroup, ShaderRef},
     sprite::Material2d,
 };
 
-pub fn app() {
-    App::new()
-        .add_plugins((Shader2dWindowPlugin {
-            shader: SdfDemoMaterial {
-                color: Color::BLUE,
-                mouse: Vec2::splat(0.),
-                shape: 1,
-            },
+pub const ID: &str = "single-sdf-distance-as-gradient";
+
+/// Present while moving the mouse across the window picks
+/// the shape. Passing a shape, or pressing a number key,
+/// turns that off.
+#[derive(Resource)]
+struct PickShapeWithMouse;
+
+pub fn app(config: GradientConfig) {
+    run_example(ID, SdfGradientPlugin { config });
+}
+
+/// Picks the shape from the mouse position unless the
+/// config picks one.
+#[derive(Default)]
+pub struct SdfGradientPlugin {
+    pub config: GradientConfig,
+}
+
+impl Plugin for SdfGradientPlugin {
+    fn build(&self, app: &mut App) {
+        let colors = self.config.colors;
+        let shape =
+            self.config.shape.unwrap_or(Shape::Circle);
+        let pick_with_mouse = self.config.shape.is_none();
+        app.add_plugins((Shader2dWindowPlugin {
+            example: ID,
+            shader: SdfDemoMaterial::new(colors, shape),
         },))
-        .add_systems(Update, (update_mouse,))
-        .run();
+        .add_systems(
+            OnEnter(ActiveExample(ID)),
+            move |mut commands: Commands| {
+                if pick_with_mouse {
+                    commands.insert_resource(PickShapeWithMouse)
+                }
+            },
+        )
+        .add_systems(
+            OnExit(ActiveExample(ID)),
+            |mut commands: Commands| {
+                commands.remove_resource::<PickShapeWithMouse>()
+            },
+        )
+        .add_systems(
+            Update,
+            (update_mouse, stop_picking_with_mouse, update_shape)
+                .run_if(in_state(ActiveExample(ID))),
+        );
+        add_current_shape(app, ID, shape);
+        add_vector_field(app, ID, self.config.vector_field);
+        add_medial_axis(app, ID, self.config.medial_axis);
+    }
+}
+
+fn update_shape(
+    mut materials: ResMut<Assets<SdfDemoMaterial>>,
+    current_shape: Res<CurrentShape>,
+) {
+    if current_shape.is_changed() {
+        for (_handle, mat) in materials.iter_mut() {
+            mat.shape = current_shape.as_u32()
+        }
+    }
+}
+
+fn stop_picking_with_mouse(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+) {
+    if keys
+        .get_just_pressed()
+        .any(|key| shape_for_key(*key).is_some())
+    {
+        commands.remove_resource::<PickShapeWithMouse>();
+    }
 }
 
 fn update_mouse(
-    window: Query<&Window, Changed<Window>>,
-    mut cursor_moved_events: EventReader<CursorMoved>,
+    mouse: Res<SdfMouse>,
     mut materials: ResMut<Assets<SdfDemoMaterial>>,
+    mut current_shape: ResMut<CurrentShape>,
+    pick_shape: Option<Res<PickShapeWithMouse>>,
 ) {
-    if window.is_empty() {
+    if !mouse.is_changed() {
         return;
     };
-    let resolution = &window.single().resolution;
-    for event in cursor_moved_events.iter() {
-        for (_handle, mat) in materials.iter_mut() {
-            mat.mouse = Vec2::new(
-                event.position.x / resolution.width(),
-                event.position.y / resolution.height(),
-            );
-            let chunk_size = resolution.width() / 6.0;
-            let chunk = event.position.x / chunk_size;
-            mat.shape = chunk.ceil() as u32;
+    for (_handle, mat) in materials.iter_mut() {
+        mat.mouse = mouse.position;
+    }
+    if pick_shape.is_some() {
+        let chunk = mouse.position.x * 6.0;
+        let shape = Shape::from_u32(chunk.ceil() as u32);
+        if current_shape.0 != shape {
+            current_shape.0 = shape;
+        }
+    }
+}
+
+impl SdfDemoMaterial {
+    pub fn new(
+        colors: GradientColors,
+        shape: Shape,
+    ) -> Self {
+        Self {
+            inside: colors.inside,
+            outside: colors.outside,
+            background: colors.background,
+            mouse: Vec2::splat(0.),
+            color_map: colors.map.as_u32(),
+            distance_range: colors.range,
+            log_scale: colors.log_scale as u32,
+            contours: Vec4::ZERO,
+            shape: shape.as_u32(),
         }
     }
 }
@@ -47,13 +141,28 @@ impl Material2d for SdfDemoMaterial {
     }
 }
 
-#[derive(AsBindGroup, TypeUuid, TypePath, Debug, Clone)]
-#[uuid = "f690fdae-d598-45ab-8225-97e2a3f056e0"]
+#[derive(AsBindGroup, TypeUuid, Reflect, Debug, Clone)]
+#[uuid = "0bca1c2a-84fe-4fa4-b873-146d2263e0dc"]
 pub struct SdfDemoMaterial {
     #[uniform(0)]
-    color: Color,
+    inside: Color,
+    #[uniform(0)]
+    outside: Color,
+    #[uniform(0)]
+    background: Color,
     #[uniform(0)]
     shape: u32,
     #[uniform(0)]
     mouse: Vec2,
+    /// A [`ColorMap`](crate::color_map::ColorMap) id.
+    #[uniform(0)]
+    color_map: u32,
+    #[uniform(0)]
+    distance_range: f32,
+    #[uniform(0)]
+    log_scale: u32,
+    /// The [`Contours`](crate::contours::Contours) to
+    /// draw, if any.
+    #[uniform(0)]
+    contours: Vec4,
 }
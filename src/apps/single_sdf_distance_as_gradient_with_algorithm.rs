@@ -1,51 +1,68 @@
 use crate::{
-    bevy_plugin_shader2d::Shader2dWindowPlugin, *,
+    bevy_plugin_shader2d::{
+        run_example, SdfMouse, Shader2dWindowPlugin,
+    },
+    formula_panel::{add_formula_panel, FormulaPanel},
+    gradient::{
+        add_current_shape, CurrentShape, GradientConfig,
+    },
+    medial_axis::add_medial_axis,
+    sdf::Shape,
+    switcher::ActiveExample,
+    vector_field::add_vector_field,
 };
 use bevy::{
-    math::Vec2Swizzles,
     prelude::*,
-    reflect::{TypePath, TypeUuid},
+    reflect::TypeUuid,
     render::render_resource::{AsBindGroup, ShaderRef},
     sprite::Material2d,
 };
 
-#[derive(Resource)]
-enum CurrentShape {
-    Circle,
-    Box,
-    EquilateralTriangle,
-    X,
-    CoolS,
+pub const ID: &str =
+    "single-sdf-distance-as-gradient-with-algorithm";
+
+pub fn app(config: GradientConfig) {
+    run_example(
+        ID,
+        SdfGradientWithAlgorithmPlugin { config },
+    );
 }
 
-impl CurrentShape {
-    fn as_u32(&self) -> u32 {
-        match self {
-            CurrentShape::Circle => 1,
-            CurrentShape::Box => 2,
-            CurrentShape::EquilateralTriangle => 3,
-            CurrentShape::X => 4,
-            CurrentShape::CoolS => 5,
-        }
-    }
+/// Starts with the box unless the config picks
+/// another shape.
+#[derive(Default)]
+pub struct SdfGradientWithAlgorithmPlugin {
+    pub config: GradientConfig,
 }
 
-pub fn app() {
-    App::new()
-        .add_plugins((Shader2dWindowPlugin {
+impl Plugin for SdfGradientWithAlgorithmPlugin {
+    fn build(&self, app: &mut App) {
+        let colors = self.config.colors;
+        let shape = self.config.shape.unwrap_or(Shape::Box);
+        app.add_plugins((Shader2dWindowPlugin {
+            example: ID,
             shader: SdfDemoMaterial {
-                color: Color::BLUE,
+                inside: colors.inside,
+                outside: colors.outside,
+                background: colors.background,
                 mouse: Vec2::splat(0.),
-                shape: 1,
+                color_map: colors.map.as_u32(),
+                distance_range: colors.range,
+                log_scale: colors.log_scale as u32,
+                contours: Vec4::ZERO,
+                shape: shape.as_u32(),
             },
         },))
-        .insert_resource(CurrentShape::Box)
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (update_mouse, ray_gizmos, update_shape),
-        )
-        .run();
+            .add_systems(
+                Update,
+                (update_mouse, update_shape)
+                    .run_if(in_state(ActiveExample(ID))),
+            );
+        add_current_shape(app, ID, shape);
+        add_vector_field(app, ID, self.config.vector_field);
+        add_medial_axis(app, ID, self.config.medial_axis);
+        add_formula_panel(app, ID, box_steps());
+    }
 }
 
 fn update_shape(
@@ -60,300 +77,124 @@ fn update_shape(
 }
 
 fn update_mouse(
-    window: Query<&Window, Changed<Window>>,
-    mut cursor_moved_events: EventReader<CursorMoved>,
+    mouse: Res<SdfMouse>,
     mut materials: ResMut<Assets<SdfDemoMaterial>>,
-    mut text: Query<&mut Text, With<MousePosition>>,
 ) {
-    if window.is_empty() {
+    if !mouse.is_changed() {
         return;
     };
-    let resolution = &window.single().resolution;
-    for event in cursor_moved_events.iter() {
-        for (_handle, mat) in materials.iter_mut() {
-            mat.mouse = Vec2::new(
-                event.position.x / resolution.width(),
-                event.position.y / resolution.height(),
-            );
-            for mut text in text.iter_mut() {
-                let coord = center_sdf(
-                    mat.mouse.xy(),
-                    Vec2::new(
-                        resolution.width(),
-                        resolution.height(),
-                    ),
-                );
-                // let distance_from_circle =
-                // sd_circle(coord);
-                text.sections[1].value =
-                    format!("{:+}", coord.x);
-                text.sections[3].value =
-                    format!("{:+}", coord.y);
-                text.sections[5].value =
-                    format!("{:+}", coord.x.abs());
-                text.sections[7].value =
-                    format!("{:+}", coord.y.abs());
-
-                let point = coord;
-                let size = Vec2::new(0.5, 0.5);
-                let abs = point.abs();
-                let distance_vector = abs - size;
-
-                let length = distance_vector
-                    .max(Vec2::ZERO)
-                    .length();
-
-                let offset = distance_vector
-                    .x
-                    .max(distance_vector.y)
-                    .min(0.0);
-
-                let final_distance = length + offset;
-
-                text.sections[9].value = format!(
-                    "x: {:+}\n  y: {:+}",
-                    distance_vector.x, distance_vector.y
-                );
-                text.sections[11].value =
-                    format!("{:+}", length);
-                text.sections[13].value =
-                    format!("{:+}", offset);
-                text.sections[15].value =
-                    format!("{:+}", final_distance);
-                // text.sections[5].value =
-                //     format!("{:+}",
-                // distance_from_circle);
-            }
-        }
+    for (_handle, mat) in materials.iter_mut() {
+        mat.mouse = mouse.position;
     }
 }
-fn ray_gizmos(
-    window: Query<&Window>,
-    materials: ResMut<Assets<SdfDemoMaterial>>,
-    mut gizmos: Gizmos,
-) {
-    if window.is_empty() {
-        return;
-    };
-    let resolution = &window.single().resolution;
-    for (_handle, mat) in materials.iter() {
-        let uv = mat.mouse.xy() * 2.0 - 1.0;
 
-        let x = uv.x * resolution.width() / 2.0;
-        let y = -uv.y * resolution.height() / 2.0;
-
-        let coord = center_sdf(
-            mat.mouse.xy(),
-            Vec2::new(
-                resolution.width(),
-                resolution.height(),
-            ),
-        );
-
-        let width_height = Vec2::new(
-            resolution.width(),
-            resolution.height(),
-        );
-
-        // let distance_from_circle = sd_circle(coord);
-
-        // let real_distance = distance_from_circle
-        //     * width_height.x.min(width_height.y)
-        //     / 2.0;
-        // // let direction = Vec2::new(x, y).normalize();
-        // // dbg!(distance_from_circle, direction);
+const BLUE: Color = Color::Rgba {
+    red: 0.122,
+    green: 0.663,
+    blue: 0.957,
+    alpha: 1.,
+};
 
-        // let direction = Vec2::new(x, y).normalize();
+/// The half size of the box.
+const SIZE: Vec2 = Vec2::new(0.5, 0.5);
 
-        let point = coord;
-        let size = Vec2::new(0.5, 0.5);
-        let abs = point.abs();
-        let distance_vector = abs - size;
-        gizmos.line_2d(
-            Vec2::new(0., 0.)
-                + size * width_height.x.min(width_height.y)
-                    / 2.0,
-            (distance_vector.max(Vec2::ZERO) + size)
-                * width_height.x.min(width_height.y)
-                / 2.0,
-            Color::Rgba {
-                red: 0.122,
-                green: 0.663,
-                blue: 0.957,
-                alpha: 1.,
-            },
-        );
-        let length =
-            distance_vector.max(Vec2::ZERO).length();
+fn distance_vector(point: Vec2) -> Vec2 {
+    point.abs() - SIZE
+}
 
-        let offset = distance_vector
-            .x
-            .max(distance_vector.y)
-            .min(0.0);
+fn offset(point: Vec2) -> f32 {
+    let distance = distance_vector(point);
+    distance.x.max(distance.y).min(0.0)
+}
 
-        if offset < 0. {
-            let render_offset = offset
-                * width_height.x.min(width_height.y)
-                / 2.0;
-            gizmos.ray_2d(
-                Vec2::new(x, y).abs(),
-                if distance_vector.x <= distance_vector.y {
-                    Vec2::new(0., -render_offset)
-                } else {
-                    Vec2::new(-render_offset, 0.)
-                },
-                Color::YELLOW,
+/// Every step of [`sd_box`], with a gizmo for each step
+/// that has a place on screen.
+fn box_steps() -> FormulaPanel {
+    FormulaPanel::default()
+        .row("mouse_position", Color::WHITE, |input| {
+            input.point()
+        })
+        .gizmo(|input, gizmos, color| {
+            let resolution = input.resolution;
+            gizmos.circle_2d(
+                input.to_screen(input.point()),
+                5.0,
+                color,
             );
-        }
-
-        let final_distance = length + offset;
-
-        gizmos.circle_2d(
-            Vec2::new(x, y).abs(),
-            5.0,
+            gizmos.line_2d(
+                Vec2::new(-resolution.x, 0.),
+                Vec2::new(resolution.x, 0.),
+                color,
+            );
+            gizmos.line_2d(
+                Vec2::new(0., -resolution.y),
+                Vec2::new(0., resolution.y),
+                color,
+            );
+        })
+        .row(
+            "mouse_position (moved to first quadrant)",
             Color::GREEN,
-        );
-
-        gizmos.circle_2d(
-            Vec2::new(x, y),
-            5.0,
-            Color::WHITE,
-        );
-        gizmos.line_2d(
-            Vec2::new(-resolution.width(), 0.),
-            Vec2::new(resolution.width(), 0.),
-            Color::WHITE,
-        );
-        gizmos.line_2d(
-            Vec2::new(0., -resolution.height()),
-            Vec2::new(0., resolution.height()),
-            Color::WHITE,
-        );
-    }
-}
-
-#[derive(Component)]
-struct MousePosition;
-
-fn setup(mut commands: Commands) {
-    let text_style = TextStyle {
-        font_size: 20.0,
-        color: Color::WHITE,
-        ..default()
-    };
-    let green_text = TextStyle {
-        font_size: 20.0,
-        color: Color::GREEN,
-        ..default()
-    };
-    let yellow_text = TextStyle {
-        font_size: 20.0,
-        color: Color::YELLOW,
-        ..default()
-    };
-    let blue_text = TextStyle {
-        font_size: 20.0,
-        color: Color::Rgba {
-            red: 0.122,
-            green: 0.663,
-            blue: 0.957,
-            alpha: 1.,
-        },
-        ..default()
-    };
-    commands
-        .spawn(NodeBundle {
-            style: Style {
-                position_type: PositionType::Absolute,
-                left: Val::Px(20.),
-                top: Val::Px(20.),
-                flex_direction: FlexDirection::Column,
-                ..default()
-            },
-            background_color: BackgroundColor(Color::BLACK),
-            ..default()
+            |input| input.point().abs(),
+        )
+        .gizmo(|input, gizmos, color| {
+            gizmos.circle_2d(
+                input.to_screen(input.point().abs()),
+                5.0,
+                color,
+            );
         })
-        .with_children(|parent| { parent.spawn((
-            TextBundle {
-                style: Style {
-                    margin: UiRect::all(Val::Px(20.)),
-                    ..default()
-                },
-                text: Text::from_sections(vec![
-                    TextSection {
-                        value: "mouse_position\n  x: "
-                            .to_string(),
-                        style: text_style.clone(),
-                    },
-                    TextSection {
-                        value: "".to_string(),
-                        style: text_style.clone(),
-                    },
-                    TextSection {
-                        value: "\n  y: ".to_string(),
-                        style: text_style.clone(),
-                    },
-                    TextSection {
-                        value: "".to_string(),
-                        style: text_style.clone(),
-                    },
-                    TextSection {
-                        value: "\n\nmouse_position (moved to first quadrant)\n  x: "
-                        .to_string(),
-                    style: green_text.clone(),
-                },
-                TextSection {
-                    value: "".to_string(),
-                    style: green_text.clone(),
-                },
-                TextSection {
-                    value: "\n  y: ".to_string(),
-                    style: green_text.clone(),
-                },
-                TextSection {
-                    value: "".to_string(),
-                    style: green_text.clone(),
-                },
-
-                TextSection {
-                    value: "\n\npoint.abs() - size (distance_vector):\n  "
-                    .to_string(),
-                style: blue_text.clone(),
-            },
-            TextSection {
-                value: "".to_string(),
-                style: blue_text.clone(),
-            },
-            TextSection {
-                value: "\ndistance.max(Vec2::ZERO).length():\n  ".to_string(),
-                style: blue_text.clone(),
-            },
-            TextSection {
-                value: "".to_string(),
-                style: blue_text.clone(),
-            },
-            TextSection {
-                value: "\n\ndistance.x.max(distance.y).min(0.0):\n  ".to_string(),
-                style: yellow_text.clone(),
-            },
-            TextSection {
-                value: "".to_string(),
-                style: yellow_text.clone(),
-            },
-            TextSection {
-                value: "\n\nfinal_distance:\n  ".to_string(),
-                style: text_style.clone(),
-            },
-            TextSection {
-                value: "".to_string(),
-                style: text_style.clone(),
-            },
-                ]),
-                ..default()
+        .row(
+            "point.abs() - size (distance_vector)",
+            BLUE,
+            |input| distance_vector(input.point()),
+        )
+        .row(
+            "distance.max(Vec2::ZERO).length()",
+            BLUE,
+            |input| {
+                distance_vector(input.point())
+                    .max(Vec2::ZERO)
+                    .length()
             },
-            MousePosition,
-        ));
-    });
+        )
+        .gizmo(|input, gizmos, color| {
+            let distance = distance_vector(input.point());
+            gizmos.line_2d(
+                input.to_screen(SIZE),
+                input.to_screen(
+                    distance.max(Vec2::ZERO) + SIZE,
+                ),
+                color,
+            );
+        })
+        .row(
+            "distance.x.max(distance.y).min(0.0)",
+            Color::YELLOW,
+            |input| offset(input.point()),
+        )
+        .gizmo(|input, gizmos, color| {
+            let point = input.point();
+            let offset = offset(point);
+            if offset >= 0. {
+                return;
+            }
+            // back to the closest side, inside the box
+            let distance = distance_vector(point);
+            let back = if distance.x <= distance.y {
+                Vec2::new(0., -offset)
+            } else {
+                Vec2::new(-offset, 0.)
+            };
+            gizmos.ray_2d(
+                input.to_screen(point.abs()),
+                input.to_screen(back),
+                color,
+            );
+        })
+        .row("final_distance", Color::WHITE, |input| {
+            sd_box(input.point())
+        })
 }
 
 /// The Material trait is very configurable, but
@@ -370,15 +211,30 @@ impl Material2d for SdfDemoMaterial {
 
 // This is the struct that will be passed to your
 // shader
-#[derive(AsBindGroup, TypeUuid, TypePath, Debug, Clone)]
-#[uuid = "f690fdae-d598-45ab-8225-97e2a3f056e0"]
+#[derive(AsBindGroup, TypeUuid, Reflect, Debug, Clone)]
+#[uuid = "05473acf-5739-4541-883f-06504314e047"]
 pub struct SdfDemoMaterial {
     #[uniform(0)]
-    color: Color,
+    inside: Color,
+    #[uniform(0)]
+    outside: Color,
+    #[uniform(0)]
+    background: Color,
     #[uniform(0)]
     shape: u32,
     #[uniform(0)]
     mouse: Vec2,
+    /// A [`ColorMap`](crate::color_map::ColorMap) id.
+    #[uniform(0)]
+    color_map: u32,
+    #[uniform(0)]
+    distance_range: f32,
+    #[uniform(0)]
+    log_scale: u32,
+    /// The [`Contours`](crate::contours::Contours) to
+    /// draw, if any.
+    #[uniform(0)]
+    contours: Vec4,
 }
 
 fn sd_circle(point: Vec2) -> f32 {
@@ -391,8 +247,6 @@ fn sd_circle(point: Vec2) -> f32 {
 }
 
 fn sd_box(point: Vec2) -> f32 {
-    let size = Vec2::new(0.5, 0.5);
-    let distance = point.abs() - size;
-    distance.max(Vec2::ZERO).length()
-        + distance.x.max(distance.y).min(0.0)
+    let distance = distance_vector(point);
+    distance.max(Vec2::ZERO).length() + offset(point)
 }
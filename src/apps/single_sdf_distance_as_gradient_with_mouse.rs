@@ -1,56 +1,72 @@
 use crate::{
-    bevy_plugin_shader2d::Shader2dWindowPlugin, *,
+    bevy_plugin_shader2d::{
+        run_example, SdfMouse, Shader2dWindowPlugin,
+    },
+    gradient::{
+        add_current_shape, CurrentShape, GradientConfig,
+    },
+    medial_axis::add_medial_axis,
+    sdf::Shape,
+    switcher::{ActiveExample, ExampleEntity},
+    vector_field::add_vector_field,
+    *,
 };
 use bevy::{
     math::Vec2Swizzles,
     prelude::*,
-    reflect::{TypePath, TypeUuid},
+    reflect::TypeUuid,
     render::render_resource::{AsBindGroup, ShaderRef},
     sprite::Material2d,
 };
 
-#[derive(Resource)]
-enum CurrentShape {
-    Circle,
-    Box,
-    EquilateralTriangle,
-    X,
-    CoolS,
+pub const ID: &str =
+    "single-sdf-distance-as-gradient-with-mouse";
+
+pub fn app(config: GradientConfig) {
+    run_example(ID, SdfGradientWithMousePlugin { config });
 }
 
-impl CurrentShape {
-    fn as_u32(&self) -> u32 {
-        match self {
-            CurrentShape::Circle => 1,
-            CurrentShape::Box => 2,
-            CurrentShape::EquilateralTriangle => 3,
-            CurrentShape::X => 4,
-            CurrentShape::CoolS => 5,
-        }
-    }
+/// Starts with the circle unless the config picks
+/// another shape.
+#[derive(Default)]
+pub struct SdfGradientWithMousePlugin {
+    pub config: GradientConfig,
 }
 
-pub fn app() {
-    App::new()
-        .add_plugins((Shader2dWindowPlugin {
+impl Plugin for SdfGradientWithMousePlugin {
+    fn build(&self, app: &mut App) {
+        let colors = self.config.colors;
+        let shape =
+            self.config.shape.unwrap_or(Shape::Circle);
+        app.add_plugins((Shader2dWindowPlugin {
+            example: ID,
             shader: SdfDemoMaterial {
-                color: Color::BLUE,
+                inside: colors.inside,
+                outside: colors.outside,
+                background: colors.background,
                 mouse: Vec2::splat(0.),
-                shape: 1,
+                color_map: colors.map.as_u32(),
+                distance_range: colors.range,
+                log_scale: colors.log_scale as u32,
+                contours: Vec4::ZERO,
+                shape: shape.as_u32(),
             },
         },))
-        .insert_resource(CurrentShape::Circle)
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (
-                update_mouse,
-                ray_gizmos,
-                update_shape,
-                button_system,
-            ),
-        )
-        .run();
+            .add_systems(OnEnter(ActiveExample(ID)), setup)
+            .add_systems(
+                Update,
+                (
+                    update_mouse,
+                    ray_gizmos,
+                    update_shape,
+                    button_system,
+                )
+                    .run_if(in_state(ActiveExample(ID))),
+            );
+        add_current_shape(app, ID, shape);
+        add_vector_field(app, ID, self.config.vector_field);
+        add_medial_axis(app, ID, self.config.medial_axis);
+    }
 }
 
 fn update_shape(
@@ -65,36 +81,31 @@ fn update_shape(
 }
 
 fn update_mouse(
-    window: Query<&Window, Changed<Window>>,
-    mut cursor_moved_events: EventReader<CursorMoved>,
+    window: Query<&Window>,
+    mouse: Res<SdfMouse>,
     mut materials: ResMut<Assets<SdfDemoMaterial>>,
     mut text: Query<&mut Text, With<MousePosition>>,
 ) {
-    if window.is_empty() {
+    if window.is_empty() || !mouse.is_changed() {
         return;
     };
     let resolution = &window.single().resolution;
-    for event in cursor_moved_events.iter() {
-        for (_handle, mat) in materials.iter_mut() {
-            mat.mouse = Vec2::new(
-                event.position.x / resolution.width(),
-                event.position.y / resolution.height(),
+    for (_handle, mat) in materials.iter_mut() {
+        mat.mouse = mouse.position;
+        for mut text in text.iter_mut() {
+            let coord = center_sdf(
+                mat.mouse.xy(),
+                Vec2::new(
+                    resolution.width(),
+                    resolution.height(),
+                ),
             );
-            for mut text in text.iter_mut() {
-                let coord = center_sdf(
-                    mat.mouse.xy(),
-                    Vec2::new(
-                        resolution.width(),
-                        resolution.height(),
-                    ),
-                );
-                text.sections[1].value =
-                    format!("{:+}", coord.x);
-                text.sections[3].value =
-                    format!("{:+}", coord.y);
-                text.sections[5].value =
-                    format!("{:+}", coord.xy().length());
-            }
+            text.sections[1].value =
+                format!("{:+}", coord.x);
+            text.sections[3].value =
+                format!("{:+}", coord.y);
+            text.sections[5].value =
+                format!("{:+}", coord.xy().length());
         }
     }
 }
@@ -124,6 +135,9 @@ fn ray_gizmos(
 #[derive(Component)]
 struct MousePosition;
 
+#[derive(Component)]
+struct ChangeShapeButton;
+
 fn setup(mut commands: Commands) {
     let text_style = TextStyle {
         font_size: 20.0,
@@ -136,7 +150,7 @@ fn setup(mut commands: Commands) {
         ..default()
     };
     commands
-        .spawn(NodeBundle {
+        .spawn((NodeBundle {
             style: Style {
                 position_type: PositionType::Absolute,
                 left: Val::Px(20.),
@@ -146,7 +160,7 @@ fn setup(mut commands: Commands) {
             },
             background_color: BackgroundColor(Color::BLACK),
             ..default()
-        })
+        }, ExampleEntity))
         .with_children(|parent| {
             parent.spawn((
                 TextBundle {
@@ -186,7 +200,7 @@ fn setup(mut commands: Commands) {
             ));
 
             parent
-                .spawn(ButtonBundle {
+                .spawn((ButtonBundle {
                     style: Style {
                         width: Val::Auto,
                         height: Val::Px(65.0),
@@ -198,7 +212,7 @@ fn setup(mut commands: Commands) {
                     border_color: BorderColor(Color::rgb(0.2, 0.2, 0.2)),
                     background_color: NORMAL_BUTTON.into(),
                     ..default()
-                })
+                }, ChangeShapeButton))
                 .with_children(|parent| {
                     parent.spawn(TextBundle::from_section(
                         "Change Shape",
@@ -226,15 +240,30 @@ impl Material2d for SdfDemoMaterial {
 
 // This is the struct that will be passed to your
 // shader
-#[derive(AsBindGroup, TypeUuid, TypePath, Debug, Clone)]
-#[uuid = "f690fdae-d598-45ab-8225-97e2a3f056e0"]
+#[derive(AsBindGroup, TypeUuid, Reflect, Debug, Clone)]
+#[uuid = "9a8d899e-90b5-4f02-a639-c8771fd2332f"]
 pub struct SdfDemoMaterial {
     #[uniform(0)]
-    color: Color,
+    inside: Color,
+    #[uniform(0)]
+    outside: Color,
+    #[uniform(0)]
+    background: Color,
     #[uniform(0)]
     shape: u32,
     #[uniform(0)]
     mouse: Vec2,
+    /// A [`ColorMap`](crate::color_map::ColorMap) id.
+    #[uniform(0)]
+    color_map: u32,
+    #[uniform(0)]
+    distance_range: f32,
+    #[uniform(0)]
+    log_scale: u32,
+    /// The [`Contours`](crate::contours::Contours) to
+    /// draw, if any.
+    #[uniform(0)]
+    contours: Vec4,
 }
 
 const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
@@ -249,7 +278,7 @@ fn button_system(
             &mut BorderColor,
             &Children,
         ),
-        (Changed<Interaction>, With<Button>),
+        (Changed<Interaction>, With<ChangeShapeButton>),
     >,
     mut current_shape: ResMut<CurrentShape>,
 ) {
@@ -264,21 +293,7 @@ fn button_system(
             Interaction::Pressed => {
                 *color = PRESSED_BUTTON.into();
                 border_color.0 = Color::WHITE;
-                *current_shape = match *current_shape {
-                    CurrentShape::Circle => {
-                        CurrentShape::Box
-                    }
-                    CurrentShape::Box => {
-                        CurrentShape::EquilateralTriangle
-                    }
-                    CurrentShape::EquilateralTriangle => {
-                        CurrentShape::X
-                    }
-                    CurrentShape::X => CurrentShape::CoolS,
-                    CurrentShape::CoolS => {
-                        CurrentShape::Circle
-                    }
-                };
+                current_shape.0 = current_shape.next();
             }
             Interaction::Hovered => {
                 *color = HOVERED_BUTTON.into();
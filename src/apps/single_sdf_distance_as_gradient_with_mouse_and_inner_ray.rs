@@ -1,53 +1,71 @@
 //! A shader and a material that uses it.
 
 use crate::{
-    bevy_plugin_shader2d::Shader2dWindowPlugin, *,
+    bevy_plugin_shader2d::{
+        run_example, SdfMouse, Shader2dWindowPlugin,
+    },
+    gradient::{
+        add_current_shape, CurrentShape, GradientConfig,
+    },
+    medial_axis::add_medial_axis,
+    sdf::{Sdf, Shape},
+    switcher::{ActiveExample, ExampleEntity},
+    vector_field::add_vector_field,
+    *,
 };
 use bevy::{
     math::Vec2Swizzles,
     prelude::*,
-    reflect::{TypePath, TypeUuid},
+    reflect::TypeUuid,
     render::render_resource::{AsBindGroup, ShaderRef},
     sprite::Material2d,
 };
 
-#[derive(Resource)]
-enum CurrentShape {
-    Circle,
-    Box,
-    EquilateralTriangle,
-    X,
-    CoolS,
+pub const ID: &str = "single-sdf-distance-as-gradient-with-mouse-and-inner-ray";
+
+pub fn app(config: GradientConfig) {
+    run_example(
+        ID,
+        SdfGradientWithInnerRayPlugin { config },
+    );
 }
 
-impl CurrentShape {
-    fn as_u32(&self) -> u32 {
-        match self {
-            CurrentShape::Circle => 1,
-            CurrentShape::Box => 2,
-            CurrentShape::EquilateralTriangle => 3,
-            CurrentShape::X => 4,
-            CurrentShape::CoolS => 5,
-        }
-    }
+/// Starts with the circle unless the config picks
+/// another shape.
+#[derive(Default)]
+pub struct SdfGradientWithInnerRayPlugin {
+    pub config: GradientConfig,
 }
 
-pub fn app() {
-    App::new()
-        .add_plugins((Shader2dWindowPlugin {
+impl Plugin for SdfGradientWithInnerRayPlugin {
+    fn build(&self, app: &mut App) {
+        let colors = self.config.colors;
+        let shape =
+            self.config.shape.unwrap_or(Shape::Circle);
+        app.add_plugins((Shader2dWindowPlugin {
+            example: ID,
             shader: SdfDemoMaterial {
-                color: Color::BLUE,
+                inside: colors.inside,
+                outside: colors.outside,
+                background: colors.background,
                 mouse: Vec2::splat(0.),
-                shape: 1,
+                color_map: colors.map.as_u32(),
+                distance_range: colors.range,
+                log_scale: colors.log_scale as u32,
+                contours: Vec4::ZERO,
+                shape: shape.as_u32(),
             },
         },))
-        .insert_resource(CurrentShape::Circle)
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (update_mouse, ray_gizmos, update_shape),
-        )
-        .run();
+            .add_systems(OnEnter(ActiveExample(ID)), setup)
+            .add_systems(
+                Update,
+                (update_mouse, ray_gizmos, update_shape)
+                    .run_if(in_state(ActiveExample(ID))),
+            );
+        add_current_shape(app, ID, shape);
+        add_vector_field(app, ID, self.config.vector_field);
+        add_medial_axis(app, ID, self.config.medial_axis);
+    }
 }
 
 fn update_shape(
@@ -62,81 +80,59 @@ fn update_shape(
 }
 
 fn update_mouse(
-    window: Query<&Window, Changed<Window>>,
-    mut cursor_moved_events: EventReader<CursorMoved>,
+    window: Query<&Window>,
+    mouse: Res<SdfMouse>,
+    shape: Res<CurrentShape>,
     mut materials: ResMut<Assets<SdfDemoMaterial>>,
     mut text: Query<&mut Text, With<MousePosition>>,
 ) {
-    if window.is_empty() {
+    if window.is_empty()
+        || !(mouse.is_changed() || shape.is_changed())
+    {
         return;
     };
     let resolution = &window.single().resolution;
-    for event in cursor_moved_events.iter() {
-        for (_handle, mat) in materials.iter_mut() {
-            mat.mouse = Vec2::new(
-                event.position.x / resolution.width(),
-                event.position.y / resolution.height(),
+    for (_handle, mat) in materials.iter_mut() {
+        mat.mouse = mouse.position;
+        for mut text in text.iter_mut() {
+            let coord = center_sdf(
+                mat.mouse.xy(),
+                Vec2::new(
+                    resolution.width(),
+                    resolution.height(),
+                ),
             );
-            for mut text in text.iter_mut() {
-                let coord = center_sdf(
-                    mat.mouse.xy(),
-                    Vec2::new(
-                        resolution.width(),
-                        resolution.height(),
-                    ),
-                );
-                let distance_from_circle = sd_circle(coord);
-                text.sections[1].value =
-                    format!("{:+}", coord.x);
-                text.sections[3].value =
-                    format!("{:+}", coord.y);
-                text.sections[5].value =
-                    format!("{:+}", distance_from_circle);
-            }
+            let distance = shape.distance(coord);
+            text.sections[1].value =
+                format!("{:+}", coord.x);
+            text.sections[3].value =
+                format!("{:+}", coord.y);
+            text.sections[5].value =
+                format!("{:+}", distance);
         }
     }
 }
+/// The ray from the mouse to the closest point on the
+/// shape, outside or inside it.
 fn ray_gizmos(
     window: Query<&Window>,
     materials: ResMut<Assets<SdfDemoMaterial>>,
+    shape: Res<CurrentShape>,
     mut gizmos: Gizmos,
 ) {
     if window.is_empty() {
         return;
     };
     let resolution = &window.single().resolution;
+    let width_height =
+        Vec2::new(resolution.width(), resolution.height());
     for (_handle, mat) in materials.iter() {
-        let uv = mat.mouse.xy() * 2.0 - 1.0;
-
-        let x = uv.x * resolution.width() / 2.0;
-        let y = -uv.y * resolution.height() / 2.0;
-
-        let coord = center_sdf(
-            mat.mouse.xy(),
-            Vec2::new(
-                resolution.width(),
-                resolution.height(),
-            ),
-        );
-
-        let width_height = Vec2::new(
-            resolution.width(),
-            resolution.height(),
-        );
-
-        let distance_from_circle = sd_circle(coord);
-
-        let real_distance = distance_from_circle
-            * width_height.x.min(width_height.y)
-            / 2.0;
-        // let direction = Vec2::new(x, y).normalize();
-        // dbg!(distance_from_circle, direction);
-
-        let direction = Vec2::new(x, y).normalize();
-
+        let coord = center_sdf(mat.mouse.xy(), width_height);
+        let closest = shape.closest_point(coord);
+        let start = scene_to_world(coord, width_height);
         gizmos.ray_2d(
-            Vec2::new(x, y),
-            -direction * real_distance,
+            start,
+            scene_to_world(closest, width_height) - start,
             Color::GREEN,
         );
     }
@@ -160,7 +156,7 @@ fn setup(mut commands: Commands) {
         ..default()
     };
     commands
-        .spawn(NodeBundle {
+        .spawn((NodeBundle {
             style: Style {
                 position_type: PositionType::Absolute,
                 left: Val::Px(20.),
@@ -170,7 +166,7 @@ fn setup(mut commands: Commands) {
             },
             background_color: BackgroundColor(Color::BLACK),
             ..default()
-        })
+        }, ExampleEntity))
         .with_children(|parent| {
             parent.spawn((
                 TextBundle {
@@ -219,22 +215,28 @@ impl Material2d for SdfDemoMaterial {
     }
 }
 
-#[derive(AsBindGroup, TypeUuid, TypePath, Debug, Clone)]
-#[uuid = "f690fdae-d598-45ab-8225-97e2a3f056e0"]
+#[derive(AsBindGroup, TypeUuid, Reflect, Debug, Clone)]
+#[uuid = "66999ecf-4531-4e7d-bb8a-e6f7d83ec06c"]
 pub struct SdfDemoMaterial {
     #[uniform(0)]
-    color: Color,
+    inside: Color,
+    #[uniform(0)]
+    outside: Color,
+    #[uniform(0)]
+    background: Color,
     #[uniform(0)]
     shape: u32,
     #[uniform(0)]
     mouse: Vec2,
-}
-
-fn sd_circle(point: Vec2) -> f32 {
-    let center = Vec2::splat(0.);
-    let radius = 0.5;
-    // point - center is so that we can "relocate" a
-    // circle because otherwise it would only
-    // exist at world origin: 0,0
-    point.distance(center) - radius
+    /// A [`ColorMap`](crate::color_map::ColorMap) id.
+    #[uniform(0)]
+    color_map: u32,
+    #[uniform(0)]
+    distance_range: f32,
+    #[uniform(0)]
+    log_scale: u32,
+    /// The [`Contours`](crate::contours::Contours) to
+    /// draw, if any.
+    #[uniform(0)]
+    contours: Vec4,
 }
@@ -1,33 +1,54 @@
 use bevy::{
-    asset::ChangeWatcher,
+    asset::{load_internal_asset, ChangeWatcher},
+    core_pipeline::clear_color::ClearColorConfig,
+    input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
-    render::render_resource::AsBindGroup,
+    reflect::{Struct, TypeUuid},
+    render::{
+        camera::Viewport,
+        mesh::Indices,
+        render_resource::{AsBindGroup, PrimitiveTopology},
+        settings::WgpuSettings,
+        view::RenderLayers,
+        RenderPlugin,
+    },
     sprite::{
         Material2d, Material2dPlugin, MaterialMesh2dBundle,
         Mesh2dHandle,
     },
-    window::WindowResolution,
+    window::{ExitCondition, WindowResolution},
+    winit::WinitPlugin,
 };
 use core::hash::Hash;
 use std::{f32::INFINITY, time::Duration};
 
-pub struct Shader2dWindowPlugin<S: Material2d> {
-    pub shader: S,
-}
+use crate::{
+    contours::{
+        set_contours, sync_contours, toggle_contours,
+        Contours,
+    },
+    switcher::{
+        add_active_example_state, despawn_example_entities,
+        ActiveExample, ExampleEntity,
+    },
+};
 
-impl<M: Material2d> Plugin for Shader2dWindowPlugin<M>
-where
-    <M as AsBindGroup>::Data: PartialEq<<M as AsBindGroup>::Data>
-        + Eq
-        + Hash
-        + Clone,
-{
+/// Handle for the shared SDF library, importable from
+/// any example shader with
+/// `#import yt_raymarch_2d::sdf center_sdf, shapes`
+pub const SDF_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(
+        Shader::TYPE_UUID,
+        0x5d2f_8c1e_a93b_4f07,
+    );
+
+/// The window, camera and shared SDF shader library the
+/// examples render into. Add it before any example plugin.
+pub struct SdfWindowPlugin;
+
+impl Plugin for SdfWindowPlugin {
     fn build(&self, app: &mut App) {
-        let mat = Material2dPlugin::<M>::default();
-        app.insert_resource(UserShader(
-            self.shader.clone(),
-        ))
-        .add_plugins(
+        app.add_plugins((
             DefaultPlugins
                 .set(AssetPlugin {
                     watch_for_changes: Some(
@@ -58,10 +79,298 @@ where
                     }),
                     ..default()
                 }),
+            SdfCorePlugin,
+        ))
+        .add_systems(Startup, spawn_camera);
+    }
+}
+
+/// [`DefaultPlugins`] without a window or a GPU, for
+/// driving the examples from tests with `App::update`.
+/// Nothing is drawn, but every system runs.
+pub struct SdfHeadlessPlugin;
+
+impl Plugin for SdfHeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                })
+                .set(RenderPlugin {
+                    wgpu_settings: WgpuSettings {
+                        backends: None,
+                        ..default()
+                    },
+                })
+                .disable::<WinitPlugin>(),
+            SdfCorePlugin,
+        ))
+        .add_systems(Startup, spawn_camera);
+    }
+}
+
+/// What the example plugins need on top of
+/// [`DefaultPlugins`]: the shared SDF shader library, the
+/// [`ActiveExample`] state, the [`SdfMouse`], zooming with
+/// the mouse wheel, the [`Contours`] and a window covering
+/// quad that follows the window's size.
+///
+/// Add it to your own app, after `DefaultPlugins` and
+/// before any example plugin, to embed the examples. It
+/// doesn't spawn a camera: the examples expect a
+/// `Camera2dBundle::default()`.
+pub struct SdfCorePlugin;
+
+impl Plugin for SdfCorePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SDF_SHADER_HANDLE,
+            "sdf.wgsl",
+            Shader::from_wgsl
+        );
+
+        add_active_example_state(app);
+        app.init_resource::<SdfMouse>()
+            .init_resource::<Contours>()
+            .add_systems(
+                Update,
+                (
+                    update_window,
+                    (pin_mouse, follow_cursor).chain(),
+                    zoom_camera,
+                    mouse_changed_on_start,
+                    toggle_contours,
+                ),
+            );
+    }
+}
+
+/// The mouse position the examples draw with, in the
+/// window covering quad's uv coordinates: from 0 to 1 left
+/// to right and top to bottom. When a
+/// [comparison](Shader2dComparePlugin) is side by side,
+/// it's in the uv coordinates of the half under the cursor.
+///
+/// It follows the cursor until P pins it in place.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct SdfMouse {
+    pub position: Vec2,
+    pub pinned: bool,
+}
+
+impl Default for SdfMouse {
+    fn default() -> Self {
+        Self {
+            position: Vec2::splat(0.5),
+            pinned: false,
+        }
+    }
+}
+
+fn pin_mouse(
+    keys: Res<Input<KeyCode>>,
+    mut mouse: ResMut<SdfMouse>,
+) {
+    if keys.just_pressed(KeyCode::P) {
+        mouse.pinned = !mouse.pinned;
+    }
+}
+
+fn follow_cursor(
+    mut cursor_moved_events: EventReader<CursorMoved>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut mouse: ResMut<SdfMouse>,
+) {
+    // cameras with a viewport only see part of the window,
+    // so they go before the one seeing all of it
+    let mut cameras = cameras.iter().collect::<Vec<_>>();
+    cameras.sort_by_key(|(camera, _)| {
+        camera.viewport.is_none()
+    });
+    for event in cursor_moved_events.iter() {
+        if mouse.pinned {
+            continue;
+        }
+        // through the camera, so the mouse stays under
+        // the cursor when zoomed in
+        let Some((world, size)) = cameras.iter().find_map(
+            |(camera, transform)| {
+                let (min, max) =
+                    camera.logical_viewport_rect()?;
+                let inside =
+                    event.position.cmpge(min).all()
+                        && event.position.cmplt(max).all();
+                if !inside {
+                    return None;
+                }
+                let world = camera.viewport_to_world_2d(
+                    transform,
+                    event.position - min,
+                )?;
+                Some((world, max - min))
+            },
+        ) else {
+            continue;
+        };
+        mouse.position = Vec2::new(
+            world.x / size.x + 0.5,
+            0.5 - world.y / size.y,
+        );
+    }
+}
+
+/// Lets a newly started example pick up the mouse position
+/// without waiting for the cursor to move.
+fn mouse_changed_on_start(
+    active: Res<State<ActiveExample>>,
+    mut mouse: ResMut<SdfMouse>,
+) {
+    if active.is_changed() {
+        mouse.set_changed();
+    }
+}
+
+/// How far the camera zooms in at most.
+pub const MAX_ZOOM: f32 = 10.;
+
+/// Zooms in and out around the cursor, never further out
+/// than the window covering quad.
+fn zoom_camera(
+    mut wheel_events: EventReader<MouseWheel>,
+    windows: Query<&Window>,
+    mut cameras: Query<
+        (&mut Transform, &mut OrthographicProjection),
+        Without<CompareCamera>,
+    >,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    for event in wheel_events.iter() {
+        let lines = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 40.,
+        };
+        for (mut transform, mut projection) in
+            cameras.iter_mut()
+        {
+            let old_scale = projection.scale;
+            let new_scale = (old_scale
+                * 0.9_f32.powf(lines))
+            .clamp(1. / MAX_ZOOM, 1.);
+            // keep the point under the cursor in place
+            let cursor = window
+                .cursor_position()
+                .map(|cursor| {
+                    Vec2::new(
+                        cursor.x - window.width() / 2.,
+                        window.height() / 2. - cursor.y,
+                    ) * old_scale
+                        + transform.translation.truncate()
+                })
+                .unwrap_or(
+                    transform.translation.truncate(),
+                );
+            let center = cursor
+                - (cursor
+                    - transform.translation.truncate())
+                    * new_scale
+                    / old_scale;
+            projection.scale = new_scale;
+            set_camera_center(
+                &mut transform,
+                window,
+                new_scale,
+                center,
+            );
+        }
+    }
+}
+
+/// Moves the camera to `center`, in pixels, keeping the
+/// view inside the window covering quad.
+pub fn set_camera_center(
+    transform: &mut Transform,
+    window: &Window,
+    scale: f32,
+    center: Vec2,
+) {
+    let limit = Vec2::new(window.width(), window.height())
+        * (1. - scale)
+        / 2.;
+    let center = center.clamp(-limit, limit);
+    transform.translation.x = center.x;
+    transform.translation.y = center.y;
+}
+
+/// Runs a single example in its own window. This is what
+/// each example's `app()` function does.
+pub fn run_example(
+    example: &'static str,
+    plugin: impl Plugin,
+) {
+    App::new()
+        .add_plugins(SdfWindowPlugin)
+        .add_plugins(plugin)
+        .insert_resource(State::new(ActiveExample(example)))
+        .run();
+}
+
+/// Covers the window with a quad drawn with `shader` while
+/// `example` is the [`ActiveExample`].
+///
+/// A `contours: Vec4` field in the material follows the
+/// [`Contours`].
+pub struct Shader2dWindowPlugin<S: Material2d> {
+    pub example: &'static str,
+    pub shader: S,
+}
+
+impl<M: Material2d + Struct> Plugin
+    for Shader2dWindowPlugin<M>
+where
+    <M as AsBindGroup>::Data: PartialEq<<M as AsBindGroup>::Data>
+        + Eq
+        + Hash
+        + Clone,
+{
+    fn build(&self, app: &mut App) {
+        let example = ActiveExample(self.example);
+        add_active_example_state(app);
+        add_material::<M>(app);
+        app.insert_resource(UserShader(
+            self.shader.clone(),
+        ))
+        .add_systems(OnEnter(example), setup::<M>)
+        .add_systems(
+            Update,
+            sync_contours::<M>.run_if(in_state(example)),
         )
-        .add_plugins((mat,))
-        .add_systems(Startup, setup::<M>)
-        .add_systems(Update, (update_window,));
+        .add_systems(
+            OnExit(example),
+            (
+                despawn_example_entities,
+                remove_materials::<M>,
+            ),
+        );
+    }
+}
+
+/// Adds the [`Material2dPlugin`] for `M`, unless an
+/// example using the same material already did.
+fn add_material<M: Material2d>(app: &mut App)
+where
+    <M as AsBindGroup>::Data: PartialEq<<M as AsBindGroup>::Data>
+        + Eq
+        + Hash
+        + Clone,
+{
+    if !app.is_plugin_added::<Material2dPlugin<M>>() {
+        app.add_plugins(Material2dPlugin::<M>::default());
     }
 }
 
@@ -94,15 +403,24 @@ fn update_window(
     }
 }
 
+/// The material each start of the example creates its
+/// quad with.
 #[derive(Resource)]
-pub struct UserShader<S: Material2d>(S);
+pub struct UserShader<S: Material2d>(pub S);
 
-fn setup<S: Material2d>(
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
+
+fn setup<S: Material2d + Struct>(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<S>>,
     shader: Res<UserShader<S>>,
+    contours: Res<Contours>,
 ) {
+    let mut material = shader.0.clone();
+    set_contours(&mut material, contours.uniform());
     // cube
     commands.spawn((
         MaterialMesh2dBundle {
@@ -113,12 +431,498 @@ fn setup<S: Material2d>(
                 }),
             )),
             transform: Transform::from_xyz(0.0, 0.0, 0.0),
-            material: materials.add(shader.0.clone()),
+            material: materials.add(material),
             ..default()
         },
         WindowCover,
+        ExampleEntity,
     ));
+}
 
-    // camera
-    commands.spawn(Camera2dBundle::default());
+/// Materials are recreated from [`UserShader`] each time
+/// the example starts, so the old ones can go.
+fn remove_materials<S: Material2d>(
+    mut materials: ResMut<Assets<S>>,
+) {
+    let ids = materials.ids().collect::<Vec<_>>();
+    for id in ids {
+        materials.remove(id);
+    }
+}
+
+/// How [`Shader2dComparePlugin`] shows its two materials.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CompareLayout {
+    /// Each material in its own half of the window, seen
+    /// through its own camera.
+    #[default]
+    SideBySide,
+    /// Both materials cover the window, the left one to the
+    /// left of a divider that can be dragged sideways and
+    /// the right one to its right.
+    Wipe,
+}
+
+impl CompareLayout {
+    pub const NAMES: [&'static str; 2] =
+        ["side-by-side", "wipe"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "side-by-side" => {
+                Some(CompareLayout::SideBySide)
+            }
+            "wipe" => Some(CompareLayout::Wipe),
+            _ => None,
+        }
+    }
+}
+
+/// Shows `left` and `right` next to each other while
+/// `example` is the [`ActiveExample`], to compare two
+/// materials, or one material with two settings.
+///
+/// Both share the [`SdfMouse`], which goes into their
+/// `mouse: Vec2` field if they have one, and the camera:
+/// zooming zooms into both.
+pub struct Shader2dComparePlugin<
+    L: Material2d,
+    R: Material2d,
+> {
+    pub example: &'static str,
+    pub left: L,
+    pub right: R,
+    pub layout: CompareLayout,
+}
+
+impl<L: Material2d + Struct, R: Material2d + Struct> Plugin
+    for Shader2dComparePlugin<L, R>
+where
+    <L as AsBindGroup>::Data: PartialEq<<L as AsBindGroup>::Data>
+        + Eq
+        + Hash
+        + Clone,
+    <R as AsBindGroup>::Data: PartialEq<<R as AsBindGroup>::Data>
+        + Eq
+        + Hash
+        + Clone,
+{
+    fn build(&self, app: &mut App) {
+        let example = ActiveExample(self.example);
+        add_active_example_state(app);
+        add_material::<L>(app);
+        add_material::<R>(app);
+        app.insert_resource(CompareShaders {
+            left: self.left.clone(),
+            right: self.right.clone(),
+            layout: self.layout,
+        })
+        .add_systems(
+            OnEnter(example),
+            setup_comparison::<L, R>,
+        )
+        .add_systems(
+            Update,
+            (
+                (drag_divider, layout_comparison).chain(),
+                follow_main_camera,
+                compare_mouse::<L, R>,
+                sync_contours::<L>,
+                sync_contours::<R>,
+            )
+                .run_if(in_state(example)),
+        )
+        .add_systems(
+            OnExit(example),
+            (
+                despawn_example_entities,
+                remove_materials::<L>,
+                remove_materials::<R>,
+                end_comparison,
+            ),
+        );
+    }
+}
+
+/// The materials each start of a comparison creates its
+/// quads with, like [`UserShader`].
+#[derive(Resource)]
+pub struct CompareShaders<L: Material2d, R: Material2d> {
+    pub left: L,
+    pub right: R,
+    pub layout: CompareLayout,
+}
+
+/// The running comparison.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub layout: CompareLayout,
+    /// Where the wipe divider is, from 0 at the left of
+    /// the window to 1 at the right.
+    pub divider: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Component)]
+struct CompareQuad(Side);
+
+/// One of the cameras of a side by side comparison. Each
+/// sees its half of the window, and follows the main
+/// camera.
+#[derive(Component)]
+pub struct CompareCamera(Side);
+
+#[derive(Component)]
+struct CompareDivider;
+
+fn setup_comparison<
+    L: Material2d + Struct,
+    R: Material2d + Struct,
+>(
+    mut commands: Commands,
+    shaders: Res<CompareShaders<L, R>>,
+    contours: Res<Contours>,
+    mut materials: ParamSet<(
+        ResMut<Assets<L>>,
+        ResMut<Assets<R>>,
+    )>,
+    mut main_cameras: Query<
+        &mut Camera2d,
+        Without<CompareCamera>,
+    >,
+) {
+    let mut left = shaders.left.clone();
+    let mut right = shaders.right.clone();
+    set_contours(&mut left, contours.uniform());
+    set_contours(&mut right, contours.uniform());
+    let left = materials.p0().add(left);
+    let right = materials.p1().add(right);
+    commands.insert_resource(Comparison {
+        layout: shaders.layout,
+        divider: 0.5,
+    });
+    // the meshes are made to fit the window by
+    // `layout_comparison`
+    match shaders.layout {
+        CompareLayout::SideBySide => {
+            commands.spawn((
+                MaterialMesh2dBundle {
+                    material: left,
+                    ..default()
+                },
+                CompareQuad(Side::Left),
+                RenderLayers::layer(1),
+                ExampleEntity,
+            ));
+            commands.spawn((
+                MaterialMesh2dBundle {
+                    material: right,
+                    ..default()
+                },
+                CompareQuad(Side::Right),
+                RenderLayers::layer(2),
+                ExampleEntity,
+            ));
+            commands.spawn((
+                Camera2dBundle {
+                    camera: Camera {
+                        order: -2,
+                        ..default()
+                    },
+                    ..default()
+                },
+                CompareCamera(Side::Left),
+                RenderLayers::layer(1),
+                UiCameraConfig { show_ui: false },
+                ExampleEntity,
+            ));
+            // the first camera already cleared the window
+            commands.spawn((
+                Camera2dBundle {
+                    camera: Camera {
+                        order: -1,
+                        ..default()
+                    },
+                    camera_2d: Camera2d {
+                        clear_color: ClearColorConfig::None,
+                    },
+                    ..default()
+                },
+                CompareCamera(Side::Right),
+                RenderLayers::layer(2),
+                UiCameraConfig { show_ui: false },
+                ExampleEntity,
+            ));
+            // the main camera only draws the ui on top
+            for mut camera in main_cameras.iter_mut() {
+                camera.clear_color = ClearColorConfig::None;
+            }
+        }
+        CompareLayout::Wipe => {
+            commands.spawn((
+                MaterialMesh2dBundle {
+                    material: left,
+                    ..default()
+                },
+                CompareQuad(Side::Left),
+                ExampleEntity,
+            ));
+            commands.spawn((
+                MaterialMesh2dBundle {
+                    material: right,
+                    transform: Transform::from_xyz(
+                        0., 0., 1.,
+                    ),
+                    ..default()
+                },
+                CompareQuad(Side::Right),
+                ExampleEntity,
+            ));
+            commands.spawn((
+                ButtonBundle {
+                    style: Style {
+                        position_type:
+                            PositionType::Absolute,
+                        left: Val::Percent(50.),
+                        top: Val::Px(0.),
+                        width: Val::Px(6.),
+                        height: Val::Percent(100.),
+                        margin: UiRect::left(Val::Px(-3.)),
+                        ..default()
+                    },
+                    background_color: Color::WHITE.into(),
+                    ..default()
+                },
+                CompareDivider,
+                ExampleEntity,
+            ));
+        }
+    }
+}
+
+fn end_comparison(
+    mut commands: Commands,
+    mut main_cameras: Query<
+        &mut Camera2d,
+        Without<CompareCamera>,
+    >,
+) {
+    commands.remove_resource::<Comparison>();
+    for mut camera in main_cameras.iter_mut() {
+        camera.clear_color = ClearColorConfig::Default;
+    }
+}
+
+fn drag_divider(
+    windows: Query<&Window>,
+    mut comparison: ResMut<Comparison>,
+    mut dividers: Query<
+        (&Interaction, &mut Style),
+        With<CompareDivider>,
+    >,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    for (interaction, mut style) in dividers.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            if let Some(cursor) = window.cursor_position() {
+                let divider = (cursor.x / window.width())
+                    .clamp(0.05, 0.95);
+                if comparison.divider != divider {
+                    comparison.divider = divider;
+                }
+            }
+        }
+        if comparison.is_changed() {
+            style.left =
+                Val::Percent(comparison.divider * 100.);
+        }
+    }
+}
+
+/// Fits the quads to the window, and in a wipe cuts the
+/// right one off at the divider.
+fn layout_comparison(
+    windows: Query<Ref<Window>>,
+    comparison: Res<Comparison>,
+    main_cameras: Query<
+        (Ref<Transform>, Ref<OrthographicProjection>),
+        (With<Camera2d>, Without<CompareCamera>),
+    >,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut quads: Query<(&CompareQuad, &mut Mesh2dHandle)>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Ok((transform, projection)) =
+        main_cameras.get_single()
+    else {
+        return;
+    };
+    if !window.is_changed()
+        && !comparison.is_changed()
+        && !transform.is_changed()
+        && !projection.is_changed()
+    {
+        return;
+    }
+    let size = Vec2::new(window.width(), window.height());
+    // the divider is on the screen, so where it cuts the
+    // quad depends on the camera
+    let divider = ((comparison.divider - 0.5)
+        * projection.scale
+        + transform.translation.x / size.x
+        + 0.5)
+        .clamp(0., 1.);
+    for (quad, mut handle) in quads.iter_mut() {
+        let mesh = match (comparison.layout, quad.0) {
+            (CompareLayout::SideBySide, _) => quad_part(
+                Vec2::new(size.x / 2., size.y),
+                0.,
+                1.,
+            ),
+            (CompareLayout::Wipe, Side::Left) => {
+                quad_part(size, 0., 1.)
+            }
+            (CompareLayout::Wipe, Side::Right) => {
+                quad_part(size, divider, 1.)
+            }
+        };
+        *handle = Mesh2dHandle::from(meshes.add(mesh));
+    }
+}
+
+/// The part of a quad of `size` from `from` to `to` across,
+/// with the uvs of that part in the whole quad.
+fn quad_part(size: Vec2, from: f32, to: f32) -> Mesh {
+    let left = (from - 0.5) * size.x;
+    let right = (to - 0.5) * size.x;
+    let top = size.y / 2.;
+    let bottom = -size.y / 2.;
+    let mut mesh =
+        Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        vec![
+            [left, bottom, 0.],
+            [left, top, 0.],
+            [right, top, 0.],
+            [right, bottom, 0.],
+        ],
+    );
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        vec![[0., 0., 1.]; 4],
+    );
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_UV_0,
+        vec![[from, 1.], [from, 0.], [to, 0.], [to, 1.]],
+    );
+    mesh.set_indices(Some(Indices::U32(vec![
+        0, 2, 1, 0, 3, 2,
+    ])));
+    mesh
+}
+
+/// Keeps the side by side cameras on their halves of the
+/// window, zoomed and moved like the main camera.
+fn follow_main_camera(
+    windows: Query<&Window>,
+    main_cameras: Query<
+        (&Transform, &OrthographicProjection),
+        (With<Camera2d>, Without<CompareCamera>),
+    >,
+    mut cameras: Query<(
+        &CompareCamera,
+        &mut Camera,
+        &mut Transform,
+        &mut OrthographicProjection,
+    )>,
+) {
+    let (Ok(window), Ok((main_transform, main_projection))) =
+        (windows.get_single(), main_cameras.get_single())
+    else {
+        return;
+    };
+    let width = window.physical_width();
+    let height = window.physical_height();
+    for (side, mut camera, mut transform, mut projection) in
+        cameras.iter_mut()
+    {
+        let viewport = match side.0 {
+            Side::Left => Viewport {
+                physical_position: UVec2::ZERO,
+                physical_size: UVec2::new(
+                    width / 2,
+                    height,
+                ),
+                ..default()
+            },
+            Side::Right => Viewport {
+                physical_position: UVec2::new(width / 2, 0),
+                physical_size: UVec2::new(
+                    width - width / 2,
+                    height,
+                ),
+                ..default()
+            },
+        };
+        if camera.viewport.as_ref().map(|viewport| {
+            (
+                viewport.physical_position,
+                viewport.physical_size,
+            )
+        }) != Some((
+            viewport.physical_position,
+            viewport.physical_size,
+        )) {
+            camera.viewport = Some(viewport);
+        }
+        // each half is half as wide as the window
+        let translation = main_transform.translation
+            * Vec3::new(0.5, 1., 1.);
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+        if projection.scale != main_projection.scale {
+            projection.scale = main_projection.scale;
+        }
+    }
+}
+
+fn compare_mouse<
+    L: Material2d + Struct,
+    R: Material2d + Struct,
+>(
+    mouse: Res<SdfMouse>,
+    mut materials: ParamSet<(
+        ResMut<Assets<L>>,
+        ResMut<Assets<R>>,
+    )>,
+) {
+    if !mouse.is_changed() {
+        return;
+    }
+    for (_handle, material) in materials.p0().iter_mut() {
+        set_mouse(material, mouse.position);
+    }
+    for (_handle, material) in materials.p1().iter_mut() {
+        set_mouse(material, mouse.position);
+    }
+}
+
+fn set_mouse(material: &mut dyn Struct, position: Vec2) {
+    if let Some(mouse) = material
+        .field_mut("mouse")
+        .and_then(|field| field.downcast_mut::<Vec2>())
+    {
+        *mouse = position;
+    }
 }
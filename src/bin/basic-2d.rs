@@ -5,6 +5,9 @@
 use std::f32::consts::PI;
 
 use bevy::prelude::*;
+use yt_raymarch_2d::marching_squares::{
+    MarchingSquares, Outline,
+};
 
 fn main() {
     App::new()
@@ -21,7 +24,12 @@ fn setup(mut commands: Commands) {
     commands.spawn(Camera2dBundle::default());
 }
 
-fn system(mut gizmos: Gizmos, time: Res<Time>) {
+fn system(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    windows: Query<&Window>,
+    mut outline: Local<Outline>,
+) {
     let center_radius = 10.;
     // The circles have 32 line-segments by default.
     gizmos.circle_2d(
@@ -49,7 +57,7 @@ fn system(mut gizmos: Gizmos, time: Res<Time>) {
     for i in 0..MAX_STEPS {
         let current_pos = ray.at(dist);
 
-        let dist_to_sdf = scene(current_pos, &mut gizmos);
+        let dist_to_sdf = scene(current_pos);
 
         gizmos.ray_2d(
             current_pos,
@@ -87,31 +95,38 @@ fn system(mut gizmos: Gizmos, time: Res<Time>) {
             break;
         }
     }
+    // outline the scene's shapes across the window,
+    // traced again only when its size changes
+    if let Ok(window) = windows.get_single() {
+        let half =
+            Vec2::new(window.width(), window.height()) / 2.;
+        outline.draw(
+            &MarchingSquares::default(),
+            &mut gizmos,
+            &scene,
+            -half,
+            half,
+            Color::WHITE,
+        );
+    }
 }
 
-fn scene(point: Vec2, gizmos: &mut Gizmos) -> f32 {
+fn scene(point: Vec2) -> f32 {
     // circle 1
     let position = 40.;
     let radius = 10.;
     let circle_one =
         sd_circle(point, Vec2::splat(position), radius);
-    gizmos.circle_2d(
-        Vec2::splat(position),
-        radius,
-        Color::WHITE,
-    );
 
     // circle 2
     let position = Vec2::new(200., 50.);
     let radius = 20.;
     let circle_two = sd_circle(point, position, radius);
-    gizmos.circle_2d(position, radius, Color::WHITE);
 
     // circle 3
     let position = Vec2::new(-50., 300.);
     let radius = 10.;
     let circle_three = sd_circle(point, position, radius);
-    gizmos.circle_2d(position, radius, Color::WHITE);
 
     // .min for each circle means we get the closest
     // circle distance
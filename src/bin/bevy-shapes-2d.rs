@@ -1,6 +1,12 @@
 //! This example demonstrates Bevy's immediate
 //! mode drawing API intended for visual
 //! debugging.
+//!
+//! The scene is the `min` of every shape's SDF, so each
+//! point's distance comes from whichever shape is closest.
+//! V shows which one that is: first the bisectors where
+//! the closest shape changes, then the regions each shape
+//! owns in its own colour as well.
 
 use std::f32::consts::PI;
 
@@ -12,6 +18,10 @@ use bevy::{
     },
     sprite::MaterialMesh2dBundle,
 };
+use yt_raymarch_2d::{
+    marching_squares::MarchingSquares,
+    voronoi::{bisectors, region_image},
+};
 
 fn main() {
     App::new()
@@ -19,8 +29,12 @@ fn main() {
             0.9, 0.3, 0.6,
         )))
         .add_plugins(DefaultPlugins)
+        .init_resource::<Voronoi>()
         .add_systems(Startup, setup)
-        .add_systems(Update, (system,))
+        .add_systems(
+            Update,
+            (system, toggle_voronoi, voronoi),
+        )
         .run();
 }
 
@@ -28,9 +42,22 @@ fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    mut images: ResMut<Assets<Image>>,
 ) {
     commands.spawn(Camera2dBundle::default());
 
+    // filled in by `voronoi` once the window's size is
+    // known
+    commands.spawn((
+        SpriteBundle {
+            texture: images.add(Image::default()),
+            transform: Transform::from_xyz(0., 0., -1.),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        VoronoiRegions,
+    ));
+
     let circle = shape::Circle::new(10.);
     commands.spawn((
         MaterialMesh2dBundle {
@@ -61,7 +88,7 @@ fn setup(
                 .into(),
             material: materials.add(ColorMaterial::from(
                 Color::Hsla {
-                    hue: 90.2,
+                    hue: 140.,
                     saturation: 0.754,
                     lightness: 0.602,
                     alpha: 1.0,
@@ -81,7 +108,7 @@ fn setup(
             mesh: meshes.add(circle.into()).into(),
             material: materials.add(ColorMaterial::from(
                 Color::Hsla {
-                    hue: 90.2,
+                    hue: 40.,
                     saturation: 0.754,
                     lightness: 0.602,
                     alpha: 1.0,
@@ -101,7 +128,7 @@ fn setup(
             mesh: meshes.add(quad.into()).into(),
             material: materials.add(ColorMaterial::from(
                 Color::Hsla {
-                    hue: 90.2,
+                    hue: 190.,
                     saturation: 0.754,
                     lightness: 0.602,
                     alpha: 1.0,
@@ -124,7 +151,7 @@ fn setup(
             mesh: meshes.add(pentagon.into()).into(),
             material: materials.add(ColorMaterial::from(
                 Color::Hsla {
-                    hue: 90.2,
+                    hue: 0.,
                     saturation: 0.754,
                     lightness: 0.602,
                     alpha: 1.0,
@@ -147,7 +174,7 @@ fn setup(
             mesh: meshes.add(hexagon.into()).into(),
             material: materials.add(ColorMaterial::from(
                 Color::Hsla {
-                    hue: 90.2,
+                    hue: 260.,
                     saturation: 0.754,
                     lightness: 0.602,
                     alpha: 1.0,
@@ -170,7 +197,7 @@ fn setup(
             mesh: meshes.add(octogon.into()).into(),
             material: materials.add(ColorMaterial::from(
                 Color::Hsla {
-                    hue: 90.2,
+                    hue: 310.,
                     saturation: 0.754,
                     lightness: 0.602,
                     alpha: 1.0,
@@ -424,6 +451,134 @@ fn system(
     }
 }
 
+/// What V shows of the shape closest to each point.
+#[derive(
+    Resource, Debug, Clone, Copy, PartialEq, Eq, Default,
+)]
+enum Voronoi {
+    #[default]
+    Off,
+    /// The bisectors, where two shapes are equally close,
+    /// over the scene.
+    Bisectors,
+    /// Every point in the colour of the closest shape,
+    /// darker outside it, under the bisectors.
+    Regions,
+}
+
+/// The sprite [`Voronoi::Regions`] is drawn on.
+#[derive(Component)]
+struct VoronoiRegions;
+
+/// The size, in pixels, of the texels of the regions.
+const REGION_TEXEL: f32 = 4.;
+/// The size, in pixels, of the cells the bisectors are
+/// traced on.
+const BISECTOR_CELL: f32 = 8.;
+
+fn toggle_voronoi(
+    keys: Res<Input<KeyCode>>,
+    mut mode: ResMut<Voronoi>,
+) {
+    if keys.just_pressed(KeyCode::V) {
+        *mode = match *mode {
+            Voronoi::Off => Voronoi::Bisectors,
+            Voronoi::Bisectors => Voronoi::Regions,
+            Voronoi::Regions => Voronoi::Off,
+        };
+    }
+}
+
+fn voronoi(
+    mode: Res<Voronoi>,
+    windows: Query<&Window>,
+    scene: Query<(
+        &Sdf,
+        &Transform,
+        &Handle<ColorMaterial>,
+    )>,
+    materials: Res<Assets<ColorMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut regions: Query<
+        (&Handle<Image>, &mut Sprite, &mut Visibility),
+        With<VoronoiRegions>,
+    >,
+    mut cached: Local<Option<(Vec2, Vec<Vec<Vec2>>)>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok((image, mut sprite, mut visibility)) =
+        regions.get_single_mut()
+    else {
+        return;
+    };
+    *visibility = if *mode == Voronoi::Regions {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    if *mode == Voronoi::Off || scene.is_empty() {
+        return;
+    }
+    let resolution =
+        Vec2::new(window.width(), window.height());
+    let (sdfs, colors): (Vec<_>, Vec<_>) = scene
+        .iter()
+        .map(|(sdf, transform, material)| {
+            let center = transform.translation.xy();
+            let color = materials
+                .get(material)
+                .map_or(Color::WHITE, |material| {
+                    material.color
+                });
+            (
+                move |point: Vec2| {
+                    sdf.dist(&point, &center)
+                },
+                color,
+            )
+        })
+        .unzip();
+    // the shapes don't move, so only a new window size
+    // needs the regions and bisectors found again
+    let stale = cached
+        .as_ref()
+        .map_or(true, |(size, _)| *size != resolution);
+    if stale {
+        let half = resolution / 2.;
+        if let Some(image) = images.get_mut(image) {
+            *image = region_image(
+                &sdfs,
+                &colors,
+                -half,
+                half,
+                (resolution / REGION_TEXEL)
+                    .ceil()
+                    .as_uvec2(),
+            );
+        }
+        sprite.custom_size = Some(resolution);
+        let marching = MarchingSquares {
+            cells: (resolution.min_element()
+                / BISECTOR_CELL) as u32,
+        };
+        *cached = Some((
+            resolution,
+            bisectors(&marching, &sdfs, -half, half),
+        ));
+    }
+    if let Some((_, polylines)) = cached.as_ref() {
+        for polyline in polylines {
+            gizmos.linestrip_2d(
+                polyline.iter().copied(),
+                Color::WHITE,
+            );
+        }
+    }
+}
+
 struct Ray {
     origin: Vec2,
     direction: Vec2,
@@ -1,22 +1,49 @@
 use bevy::prelude::*;
 use std::f32::consts::FRAC_PI_4;
-
+use yt_raymarch_2d::{
+    marching_squares::{MarchingSquares, Outline},
+    storyboard::{
+        StepThrough, Storyboard, StoryboardPlugin,
+    },
+};
+
+/// Steps through on its own, or plays the storyboard
+/// passed as the first argument, whose `step` keyframes
+/// pick how many steps are shown.
 fn main() {
-    App::new()
-        .insert_resource(ClearColor(Color::rgb(
-            0.9, 0.3, 0.6,
-        )))
-        .add_plugins(DefaultPlugins)
-        .add_systems(Startup, setup)
-        .add_systems(Update, (system,))
-        .run();
+    let mut app = App::new();
+    app.insert_resource(ClearColor(Color::rgb(
+        0.9, 0.3, 0.6,
+    )))
+    .add_plugins(DefaultPlugins)
+    .add_systems(Startup, setup)
+    .add_systems(Update, (system,));
+    if let Some(file) = std::env::args().nth(1) {
+        let storyboard = std::fs::read_to_string(&file)
+            .map_err(|error| error.to_string())
+            .and_then(|text| {
+                Storyboard::from_ron(&text)
+                    .map_err(|error| error.to_string())
+            })
+            .unwrap_or_else(|error| {
+                panic!("{file}: {error}")
+            });
+        app.add_plugins(StoryboardPlugin { storyboard });
+    }
+    app.run();
 }
 
 fn setup(mut commands: Commands) {
     commands.spawn(Camera2dBundle::default());
 }
 
-fn system(mut gizmos: Gizmos, time: Res<Time>) {
+fn system(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    step: Option<Res<StepThrough>>,
+    windows: Query<&Window>,
+    mut outline: Local<Outline>,
+) {
     let ray_direction = Vec2::from_angle(FRAC_PI_4 - 0.3);
 
     let ray = Ray {
@@ -30,13 +57,17 @@ fn system(mut gizmos: Gizmos, time: Res<Time>) {
     // if it takes more than 10 rays to hit something,
     // then we're out of luck
 
-    let MAX_STEPS =
-        (time.elapsed_seconds() % 10.).floor() as i32;
+    let MAX_STEPS = match step {
+        Some(step) => step.0.floor() as i32,
+        None => {
+            (time.elapsed_seconds() % 10.).floor() as i32
+        }
+    };
     let mut dist = 0.0;
     for i in 0..MAX_STEPS {
         let current_pos = ray.at(dist);
 
-        let dist_to_sdf = scene(current_pos, &mut gizmos);
+        let dist_to_sdf = scene(current_pos);
 
         gizmos.ray_2d(
             current_pos,
@@ -74,22 +105,26 @@ fn system(mut gizmos: Gizmos, time: Res<Time>) {
             break;
         }
     }
-    // hack to render gizmos for sdf shapes
-    scene(Vec2::new(0., 0.), &mut gizmos);
+    // outline the scene's shapes across the window,
+    // traced again only when its size changes
+    if let Ok(window) = windows.get_single() {
+        let half =
+            Vec2::new(window.width(), window.height()) / 2.;
+        outline.draw(
+            &MarchingSquares::default(),
+            &mut gizmos,
+            &scene,
+            -half,
+            half,
+            Color::WHITE,
+        );
+    }
 }
 
-fn scene(point: Vec2, gizmos: &mut Gizmos) -> f32 {
+fn scene(point: Vec2) -> f32 {
     let position = Vec2::new(30., 100.);
     let radius = 100.;
-    let rect =
-        sd_rect(point, position, Vec2::splat(radius));
-    gizmos.rect_2d(
-        position,
-        0.,
-        Vec2::splat(radius),
-        Color::WHITE,
-    );
-    rect
+    sd_rect(point, position, Vec2::splat(radius))
 }
 struct Ray {
     origin: Vec2,
@@ -0,0 +1,273 @@
+//! Renders one of the demo shapes to a PNG on the CPU.
+//!
+//! ```text
+//! render-sdf <shape> [options]
+//!
+//!   shape                circle, box, triangle, x or cool-s
+//!   --mode <mode>        gradient (default), border,
+//!                        circle, steps or exactness
+//!   --mouse <x>,<y>      mouse position in uv space, 0..1,
+//!                        where radial rays start in steps
+//!   --lights <on|off>    fill colour for the circle mode
+//!   --thickness <n>      half-width of the border band,
+//!                        default 0.005
+//!   --color-map <map>    gradient (default), diverging,
+//!                        viridis, cividis or greyscale
+//!   --range <distance>   where the colours end, default 1
+//!   --log-scale <on|off> spread out small distances
+//!   --size <w>x<h>       output resolution, default 600x600
+//!   --supersample <n>    n*n samples per pixel, default 1
+//!   --strategy <name>    sphere (default) or relaxed
+//!   --relaxation <n>     relaxed step factor, default 1.6
+//!   --projection <name>  radial (default) or orthographic
+//!   --angle <degrees>    direction of orthographic rays
+//!   --color-by <name>    steps (default) or termination
+//!   --max-steps <n>      steps before a ray gives up, 64
+//!   --tolerance <n>      how far |∇d| can be from 1 in
+//!                        exactness, default 0.01
+//!   --contours <spacing>[,<thickness>[,<major>[,<fade>]]]
+//!                        draw isolines, eg: 0.1,1,5,4
+//!   -o, --output <path>  defaults to <shape>.png
+//! ```
+
+use bevy::prelude::*;
+use yt_raymarch_2d::{
+    center_sdf,
+    color_map::ColorMap,
+    contours::Contours,
+    cpu_renderer::{ColorMode, CpuRenderer},
+    exactness::ExactnessCheck,
+    gradient::GradientColors,
+    raymarch::{
+        ColorBy, Projection, StepHeatmap, Strategy,
+    },
+    sdf::Shape,
+};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let Some(shape_name) = args.next() else {
+        exit_with_usage("missing shape");
+    };
+    let Some(shape) = Shape::from_name(&shape_name) else {
+        exit_with_usage(&format!("unknown shape {shape_name}"))
+    };
+
+    let mut mode = "gradient".to_string();
+    let mut mouse = Vec2::splat(0.5);
+    let mut lights = true;
+    let mut thickness = 0.005;
+    let mut size = UVec2::splat(600);
+    let mut supersampling = 1;
+    let mut colors = GradientColors::default();
+    let mut contours = Contours::default();
+    let mut heatmap = StepHeatmap::default();
+    let mut check = ExactnessCheck::default();
+    let mut output = format!("{shape_name}.png");
+
+    while let Some(flag) = args.next() {
+        let Some(value) = args.next() else {
+            exit_with_usage(&format!("{flag} needs a value"));
+        };
+        match flag.as_str() {
+            "--mode" => mode = value,
+            "--mouse" => mouse = parse_pair(&value, ','),
+            "--lights" => {
+                lights = parse_switch(&flag, &value)
+            }
+            "--thickness" => {
+                thickness = parse_number(&flag, &value)
+            }
+            "--color-map" => {
+                colors.map = ColorMap::from_name(&value)
+                    .unwrap_or_else(|| {
+                        exit_with_usage(&format!(
+                            "unknown colour map {value}"
+                        ))
+                    })
+            }
+            "--range" => {
+                colors.range = value.parse().unwrap_or_else(
+                    |_| {
+                        exit_with_usage(
+                            "--range expects a number",
+                        )
+                    },
+                )
+            }
+            "--log-scale" => {
+                colors.log_scale = parse_switch(&flag, &value)
+            }
+            "--size" => size = parse_size(&value),
+            "--supersample" => {
+                supersampling = value.parse().unwrap_or_else(
+                    |_| {
+                        exit_with_usage(
+                            "--supersample expects a number",
+                        )
+                    },
+                )
+            }
+            "--strategy" => {
+                heatmap.marcher.strategy =
+                    Strategy::from_name(&value)
+                        .unwrap_or_else(|| {
+                            exit_with_usage(&format!(
+                                "unknown strategy {value}"
+                            ))
+                        })
+            }
+            "--relaxation" => {
+                heatmap.marcher.relaxation =
+                    parse_number(&flag, &value)
+            }
+            "--projection" => {
+                heatmap.projection =
+                    Projection::from_name(&value)
+                        .unwrap_or_else(|| {
+                            exit_with_usage(&format!(
+                                "unknown projection {value}"
+                            ))
+                        })
+            }
+            "--angle" => {
+                heatmap.angle = parse_number(&flag, &value)
+            }
+            "--color-by" => {
+                heatmap.color_by = ColorBy::from_name(&value)
+                    .unwrap_or_else(|| {
+                        exit_with_usage(&format!(
+                            "unknown --color-by {value}"
+                        ))
+                    })
+            }
+            "--max-steps" => {
+                heatmap.marcher.max_steps =
+                    parse_count(&flag, &value)
+            }
+            "--tolerance" => {
+                check.tolerance = parse_number(&flag, &value)
+            }
+            "--contours" => contours = parse_contours(&value),
+            "-o" | "--output" => output = value,
+            other => exit_with_usage(&format!(
+                "unknown option {other}"
+            )),
+        }
+    }
+
+    let mode = match mode.as_str() {
+        "gradient" => ColorMode::Gradient,
+        "border" => ColorMode::Border { mouse, thickness },
+        "circle" => ColorMode::Circle { mouse, lights },
+        "steps" => ColorMode::Steps(StepHeatmap {
+            origin: center_sdf(
+                mouse,
+                Vec2::new(size.x as f32, size.y as f32),
+            ),
+            ..heatmap
+        }),
+        "exactness" => ColorMode::Exactness(check),
+        other => {
+            exit_with_usage(&format!("unknown mode {other}"))
+        }
+    };
+
+    CpuRenderer::new(size.x, size.y)
+        .with_supersampling(supersampling)
+        .with_mode(mode)
+        .with_colors(colors)
+        .with_contours(contours)
+        .save_png(&shape, &output)
+        .unwrap_or_else(|err| {
+            eprintln!("failed to write {output}: {err}");
+            std::process::exit(1);
+        });
+}
+
+fn parse_pair(value: &str, separator: char) -> Vec2 {
+    value
+        .split_once(separator)
+        .and_then(|(x, y)| {
+            Some(Vec2::new(x.parse().ok()?, y.parse().ok()?))
+        })
+        .unwrap_or_else(|| {
+            exit_with_usage(&format!(
+                "expected two numbers separated by '{separator}', got {value}"
+            ))
+        })
+}
+
+fn parse_number(flag: &str, value: &str) -> f32 {
+    value.parse().unwrap_or_else(|_| {
+        exit_with_usage(&format!("{flag} expects a number"))
+    })
+}
+
+/// A whole number above 0.
+fn parse_count(flag: &str, value: &str) -> u32 {
+    match value.parse() {
+        Ok(count) if count > 0 => count,
+        _ => exit_with_usage(&format!(
+            "{flag} expects a whole number above 0, got {value}"
+        )),
+    }
+}
+
+/// A width and a height in pixels, like `600x400`.
+fn parse_size(value: &str) -> UVec2 {
+    let Some((width, height)) = value.split_once('x')
+    else {
+        exit_with_usage(&format!(
+            "--size expects WxH, got {value}"
+        ))
+    };
+    UVec2::new(
+        parse_count("--size", width),
+        parse_count("--size", height),
+    )
+}
+
+fn parse_switch(flag: &str, value: &str) -> bool {
+    match value {
+        "on" => true,
+        "off" => false,
+        _ => exit_with_usage(&format!(
+            "{flag} expects on or off, got {value}"
+        )),
+    }
+}
+
+/// Up to four numbers separated by commas, leaving the
+/// rest at their defaults.
+fn parse_contours(value: &str) -> Contours {
+    let mut contours = Contours {
+        enabled: true,
+        ..Contours::default()
+    };
+    for (index, number) in value.split(',').enumerate() {
+        let Ok(number) = number.parse::<f32>() else {
+            exit_with_usage(&format!(
+                "--contours expects numbers, got {value}"
+            ))
+        };
+        match index {
+            0 => contours.spacing = number,
+            1 => contours.thickness = number,
+            2 => contours.major = number as u32,
+            3 => contours.fade = number,
+            _ => exit_with_usage(
+                "--contours takes up to four numbers",
+            ),
+        }
+    }
+    contours
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{message}");
+    eprintln!(
+        "usage: render-sdf <circle|box|triangle|x|cool-s> [--mode gradient|border|circle|steps|exactness] [--mouse x,y] [--lights on|off] [--thickness N] [--color-map gradient|diverging|viridis|cividis|greyscale] [--range D] [--log-scale on|off] [--strategy sphere|relaxed] [--relaxation N] [--projection radial|orthographic] [--angle DEGREES] [--color-by steps|termination] [--max-steps N] [--tolerance N] [--size WxH] [--supersample N] [--contours spacing,thickness,major,fade] [-o out.png]"
+    );
+    std::process::exit(2);
+}
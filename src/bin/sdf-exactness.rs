@@ -0,0 +1,43 @@
+//! Reports whether each demo shape, and a few ways of
+//! combining shapes, is an exact distance, a bound, or not
+//! Lipschitz-safe.
+//!
+//! ```text
+//! sdf-exactness [tolerance]
+//! ```
+
+use bevy::prelude::*;
+use yt_raymarch_2d::exactness::{
+    primitives, ExactnessCheck,
+};
+
+fn main() {
+    let mut check = ExactnessCheck::default();
+    if let Some(tolerance) = std::env::args().nth(1) {
+        check.tolerance =
+            tolerance.parse().unwrap_or_else(|_| {
+                eprintln!(
+                    "usage: sdf-exactness [tolerance]"
+                );
+                std::process::exit(2);
+            });
+    }
+    println!(
+        "{:<30} {:<19} {:>8} {:>8} {:>8}",
+        "sdf", "", "|∇d| min", "max", "flagged"
+    );
+    for (name, sdf) in primitives() {
+        let report = check.check(
+            &*sdf,
+            Vec2::splat(-1.),
+            Vec2::splat(1.),
+        );
+        println!(
+            "{name:<30} {:<19} {:>8.3} {:>8.3} {:>7.2}%",
+            report.exactness.name(),
+            report.min_gradient,
+            report.max_gradient,
+            report.flagged * 100.,
+        );
+    }
+}
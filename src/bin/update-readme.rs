@@ -0,0 +1,29 @@
+//! Regenerates the list of live demos in README.md from the
+//! example registry. Run it after adding or renaming an
+//! example.
+
+use yt_raymarch_2d::registry;
+
+const START: &str = "<!-- examples:start -->";
+const END: &str = "<!-- examples:end -->";
+
+fn main() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/README.md");
+    let readme = std::fs::read_to_string(path)
+        .expect("failed to read README.md");
+
+    let (Some(start), Some(end)) =
+        (readme.find(START), readme.find(END))
+    else {
+        panic!("README.md is missing the {START} and {END} markers");
+    };
+
+    let updated = format!(
+        "{}{START}\n{}\n{}",
+        &readme[..start],
+        registry::readme_list(),
+        &readme[end..]
+    );
+    std::fs::write(path, updated)
+        .expect("failed to write README.md");
+}
@@ -0,0 +1,145 @@
+//! Colour maps for signed distances, picked with the
+//! `color_map` parameter of the gradient examples.
+//!
+//! A distance is first scaled to -1..1 by
+//! [`signed_position`], over a range and optionally
+//! logarithmically, then either faded from the background
+//! to the inside or outside colour, or looked up in a
+//! colour map running from inside (0) to outside (1).
+//! `distance_color` in `sdf.wgsl` does the same on the GPU,
+//! with the same stops.
+
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMap {
+    /// The inside, outside and background colours of
+    /// [`GradientColors`](crate::gradient::GradientColors).
+    #[default]
+    Gradient,
+    /// Blue through grey to red.
+    Diverging,
+    /// Perceptually uniform purple through green to yellow.
+    Viridis,
+    /// Like viridis, but designed to look the same with
+    /// colour blindness: blue through grey to yellow.
+    Cividis,
+    Greyscale,
+}
+
+/// How much the smallest distances are spread out by
+/// [`signed_position`] with `log_scale`.
+const LOG_BASE: f32 = 100.;
+
+/// Five evenly spaced sRGB stops for each colour map after
+/// `Gradient`, in the order of [`ColorMap::NAMES`].
+const STOPS: [[[f32; 3]; 5]; 4] = [
+    [
+        [0.231, 0.298, 0.753],
+        [0.553, 0.690, 0.996],
+        [0.867, 0.867, 0.867],
+        [0.957, 0.604, 0.482],
+        [0.706, 0.016, 0.149],
+    ],
+    [
+        [0.267, 0.005, 0.329],
+        [0.231, 0.322, 0.545],
+        [0.129, 0.569, 0.549],
+        [0.369, 0.788, 0.384],
+        [0.992, 0.906, 0.145],
+    ],
+    [
+        [0.000, 0.133, 0.306],
+        [0.255, 0.302, 0.420],
+        [0.486, 0.482, 0.471],
+        [0.737, 0.686, 0.435],
+        [0.996, 0.910, 0.220],
+    ],
+    [
+        [0.00, 0.00, 0.00],
+        [0.25, 0.25, 0.25],
+        [0.50, 0.50, 0.50],
+        [0.75, 0.75, 0.75],
+        [1.00, 1.00, 1.00],
+    ],
+];
+
+impl ColorMap {
+    /// The names the `color_map` parameter takes, in the
+    /// order of the ids the shaders use.
+    pub const NAMES: [&'static str; 5] = [
+        "gradient",
+        "diverging",
+        "viridis",
+        "cividis",
+        "greyscale",
+    ];
+
+    const ALL: [ColorMap; 5] = [
+        ColorMap::Gradient,
+        ColorMap::Diverging,
+        ColorMap::Viridis,
+        ColorMap::Cividis,
+        ColorMap::Greyscale,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        let index =
+            Self::NAMES.iter().position(|n| *n == name)?;
+        Some(Self::ALL[index])
+    }
+
+    pub fn name(&self) -> &'static str {
+        Self::NAMES[self.as_u32() as usize]
+    }
+
+    /// The id the shaders' `color_map` uniform takes.
+    pub fn as_u32(&self) -> u32 {
+        *self as u32
+    }
+
+    /// The five evenly spaced sRGB stops, from inside to
+    /// outside. `None` for [`ColorMap::Gradient`], which
+    /// has no stops of its own.
+    pub fn stops(&self) -> Option<&'static [[f32; 3]; 5]> {
+        STOPS.get(self.as_u32().checked_sub(1)? as usize)
+    }
+
+    /// The linear colour at `t`, from 0 (inside) to 1
+    /// (outside), between the [stops](ColorMap::stops).
+    pub fn ramp(&self, t: f32) -> Option<Vec3> {
+        let stops = self.stops()?;
+        let x = t.clamp(0., 1.) * 4.;
+        let i = (x as usize).min(3);
+        let srgb = Vec3::from(stops[i])
+            .lerp(Vec3::from(stops[i + 1]), x - i as f32);
+        Some(Vec3::new(
+            srgb_to_linear(srgb.x),
+            srgb_to_linear(srgb.y),
+            srgb_to_linear(srgb.z),
+        ))
+    }
+}
+
+/// Scales `distance` to -1..1, reaching the ends at
+/// `range`. `log_scale` spreads out the small distances.
+pub fn signed_position(
+    distance: f32,
+    range: f32,
+    log_scale: bool,
+) -> f32 {
+    let mut magnitude = distance.abs() / range.max(1e-6);
+    if log_scale {
+        magnitude = (1. + (LOG_BASE - 1.) * magnitude).ln()
+            / LOG_BASE.ln();
+    }
+    magnitude.min(1.) * distance.signum()
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
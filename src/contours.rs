@@ -0,0 +1,210 @@
+//! Isolines over any example: a line every `spacing` units
+//! of distance, every `major`th one stronger, the surface
+//! itself highlighted, and the colour fading exponentially
+//! towards the surface.
+//!
+//! The settings go to the shaders in a `contours: Vec4`
+//! material field, which `draw_contours` in `sdf.wgsl`
+//! reads. [`Contours::apply`] is the same formula for the
+//! [CPU renderer](crate::cpu_renderer).
+
+use bevy::{
+    prelude::*, reflect::Struct, sprite::Material2d,
+};
+
+use crate::params::Params;
+
+/// The contour settings every example draws with. C turns
+/// them on and off.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Contours {
+    pub enabled: bool,
+    /// The distance between two lines.
+    pub spacing: f32,
+    /// The width of a line, in pixels.
+    pub thickness: f32,
+    /// Every how many lines one is a major line. 0 draws
+    /// no major lines.
+    pub major: u32,
+    /// How quickly the colour fades to black towards the
+    /// surface. 0 doesn't fade.
+    pub fade: f32,
+}
+
+impl Default for Contours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            spacing: 0.1,
+            thickness: 1.,
+            major: 5,
+            fade: 4.,
+        }
+    }
+}
+
+impl Contours {
+    /// Reads the `contour` [view
+    /// parameters](crate::registry::VIEW_PARAMS).
+    pub fn from_params(params: &Params) -> Self {
+        Self {
+            enabled: params.get("contours"),
+            spacing: params.get("contour_spacing"),
+            thickness: params.get("contour_thickness"),
+            major: params.get("contour_major"),
+            fade: params.get("contour_fade"),
+        }
+    }
+
+    /// The parameters that differ from the defaults, for
+    /// a link.
+    pub fn to_params(&self) -> Vec<(&'static str, String)> {
+        let default = Contours::default();
+        let mut params = vec![];
+        if self.enabled {
+            params.push(("contours", "true".to_string()));
+        }
+        if self.spacing != default.spacing {
+            params.push((
+                "contour_spacing",
+                self.spacing.to_string(),
+            ));
+        }
+        if self.thickness != default.thickness {
+            params.push((
+                "contour_thickness",
+                self.thickness.to_string(),
+            ));
+        }
+        if self.major != default.major {
+            params.push((
+                "contour_major",
+                self.major.to_string(),
+            ));
+        }
+        if self.fade != default.fade {
+            params.push((
+                "contour_fade",
+                self.fade.to_string(),
+            ));
+        }
+        params
+    }
+
+    /// The `contours` uniform: spacing, thickness, major
+    /// and fade, with a spacing of 0 when turned off.
+    pub fn uniform(&self) -> Vec4 {
+        if !self.enabled {
+            return Vec4::ZERO;
+        }
+        Vec4::new(
+            self.spacing,
+            self.thickness,
+            self.major as f32,
+            self.fade,
+        )
+    }
+
+    /// Draws the contours over `color` at `distance`, as
+    /// `draw_contours` does. `width` is the distance one
+    /// pixel covers.
+    pub fn apply(
+        &self,
+        color: Vec4,
+        distance: f32,
+        width: f32,
+    ) -> Vec4 {
+        if !self.enabled || self.spacing <= 0. {
+            return color;
+        }
+        let width = width.max(1e-6);
+        let line = |offset: f32, thickness: f32| {
+            (thickness * 0.5 - offset / width + 0.5)
+                .clamp(0., 1.)
+        };
+        let fade = if self.fade > 0. {
+            1. - (-self.fade * distance.abs()).exp()
+        } else {
+            1.
+        };
+        let mut rgb = color.truncate() * fade;
+        // black lines over light colours, white over dark
+        // ones
+        let luminance =
+            rgb.dot(Vec3::new(0.2126, 0.7152, 0.0722));
+        let ink = if luminance < 0.18 {
+            Vec3::ONE
+        } else {
+            Vec3::ZERO
+        };
+        rgb = rgb.lerp(
+            ink,
+            0.4 * line(
+                nearest_line(distance, self.spacing),
+                self.thickness,
+            ),
+        );
+        if self.major > 0 {
+            rgb = rgb.lerp(
+                ink,
+                0.8 * line(
+                    nearest_line(
+                        distance,
+                        self.spacing * self.major as f32,
+                    ),
+                    self.thickness * 1.5,
+                ),
+            );
+        }
+        rgb = rgb.lerp(
+            Vec3::ONE,
+            line(distance.abs(), self.thickness * 2.),
+        );
+        rgb.extend(color.w)
+    }
+}
+
+/// How far `distance` is from the closest multiple of
+/// `spacing`.
+fn nearest_line(distance: f32, spacing: f32) -> f32 {
+    ((distance / spacing + 0.5).rem_euclid(1.) - 0.5).abs()
+        * spacing
+}
+
+/// C turns the contours on and off.
+pub fn toggle_contours(
+    keys: Res<Input<KeyCode>>,
+    mut contours: ResMut<Contours>,
+) {
+    if keys.just_pressed(KeyCode::C) {
+        contours.enabled = !contours.enabled;
+    }
+}
+
+/// Copies [`Contours`] into the `contours: Vec4` field of
+/// every `M` material that has one, when they change.
+pub fn sync_contours<M: Material2d + Struct>(
+    contours: Res<Contours>,
+    mut materials: ResMut<Assets<M>>,
+) {
+    if !contours.is_changed() {
+        return;
+    }
+    for (_handle, material) in materials.iter_mut() {
+        set_contours(material, contours.uniform());
+    }
+}
+
+/// Sets the `contours: Vec4` field of `material`, if it
+/// has one.
+pub(crate) fn set_contours(
+    material: &mut dyn Struct,
+    uniform: Vec4,
+) {
+    if let Some(field) = material
+        .field_mut("contours")
+        .and_then(|field| field.downcast_mut::<Vec4>())
+    {
+        *field = uniform;
+    }
+}
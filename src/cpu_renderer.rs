@@ -0,0 +1,219 @@
+//! A CPU reference renderer for SDF scenes, for producing
+//! and checking the demo pictures where there's no GPU.
+//!
+//! Pixels are mapped to scene coordinates with
+//! [`center_sdf`], exactly as the example shaders map
+//! `mesh.uv`, and coloured with the same formulas as the
+//! shaders, [contours](crate::contours) included.
+
+use std::path::Path;
+
+use bevy::prelude::*;
+use image::{ImageResult, Rgba, RgbaImage};
+
+use crate::{
+    center_sdf, contours::Contours,
+    exactness::ExactnessCheck, gradient::GradientColors,
+    raymarch::StepHeatmap, sdf::Sdf,
+};
+
+/// How a distance is turned into a colour.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorMode {
+    /// Black to orange outside the shape and black to blue
+    /// inside, as in the `single-sdf-distance-as-gradient`
+    /// shaders, or whichever colours and colour map
+    /// [`CpuRenderer::colors`] picks.
+    Gradient,
+    /// White outside, black inside, with a green band at
+    /// the mouse's distance to the scene, as in
+    /// `single-sdf-distance-as-border`.
+    Border {
+        /// Mouse position in uv space (0..1, y down), as
+        /// stored in the `mouse` uniform.
+        mouse: Vec2,
+        /// How far either side of the mouse's distance the
+        /// band reaches, the material's `thickness`.
+        thickness: f32,
+    },
+    /// The shape filled with white (lights on) or black
+    /// (lights off) and a green circle around the mouse
+    /// whose radius is the mouse's distance to the scene,
+    /// as in `single-sdf-distance-as-circle`.
+    Circle { mouse: Vec2, lights: bool },
+    /// The steps a ray marched for each pixel takes, or why
+    /// it stops, as in `raymarch-cost`.
+    Steps(StepHeatmap),
+    /// How far the length of the gradient is from 1: blue
+    /// where the SDF is only a bound, red where it
+    /// overestimates, see [`crate::exactness`].
+    Exactness(ExactnessCheck),
+}
+
+pub struct CpuRenderer {
+    pub width: u32,
+    pub height: u32,
+    /// Samples per pixel along each axis, so `2` takes
+    /// four samples per pixel.
+    pub supersampling: u32,
+    pub mode: ColorMode,
+    /// The colours of [`ColorMode::Gradient`].
+    pub colors: GradientColors,
+    /// Isolines drawn over the mode's colours, off by
+    /// default.
+    pub contours: Contours,
+}
+
+impl CpuRenderer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            supersampling: 1,
+            mode: ColorMode::Gradient,
+            colors: GradientColors::default(),
+            contours: Contours::default(),
+        }
+    }
+
+    pub fn with_supersampling(mut self, samples: u32) -> Self {
+        self.supersampling = samples.max(1);
+        self
+    }
+
+    pub fn with_mode(mut self, mode: ColorMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_colors(mut self, colors: GradientColors) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    pub fn with_contours(mut self, contours: Contours) -> Self {
+        self.contours = contours;
+        self
+    }
+
+    fn resolution(&self) -> Vec2 {
+        Vec2::new(self.width as f32, self.height as f32)
+    }
+
+    /// The colour the shader would return for a point in
+    /// scene coordinates. Like shader output this is linear
+    /// and unclamped.
+    pub fn shade(
+        &self,
+        scene: &impl Sdf,
+        coord: Vec2,
+    ) -> Vec4 {
+        let distance = scene.distance(coord);
+        let black = Vec4::new(0., 0., 0., 1.);
+        let green = Vec4::new(0., 1., 0., 1.);
+        // like fwidth: the distance between two pixels
+        let width = 2. / self.resolution().min_element();
+        let contours = |color: Vec4| {
+            self.contours.apply(color, distance, width)
+        };
+        match self.mode {
+            ColorMode::Gradient => {
+                contours(self.colors.color(distance))
+            }
+            ColorMode::Border { mouse, thickness } => {
+                let mouse_distance = scene.distance(
+                    center_sdf(mouse, self.resolution()),
+                );
+                if (distance - mouse_distance).abs()
+                    < thickness
+                {
+                    green
+                } else {
+                    let output = if distance < 0.00001 {
+                        0.
+                    } else {
+                        1.
+                    };
+                    contours(Vec3::splat(output).extend(1.0))
+                }
+            }
+            ColorMode::Circle { mouse, lights } => {
+                let mouse_coord =
+                    center_sdf(mouse, self.resolution());
+                let circle = (coord - mouse_coord).length()
+                    - scene.distance(mouse_coord);
+                if distance < 0. {
+                    let lights = if lights { 1. } else { 0. };
+                    contours(Vec3::splat(lights).extend(1.0))
+                } else if circle.abs() < 0.005 {
+                    green
+                } else {
+                    contours(black)
+                }
+            }
+            ColorMode::Steps(heatmap) => {
+                contours(heatmap.color(scene, coord, width))
+            }
+            ColorMode::Exactness(check) => {
+                contours(check.color(scene, coord, width))
+            }
+        }
+    }
+
+    /// Averages `supersampling`² evenly spaced samples
+    /// inside the pixel at `x`, `y` (counted from the top
+    /// left, like uvs).
+    pub fn pixel(&self, scene: &impl Sdf, x: u32, y: u32) -> Vec4 {
+        let samples = self.supersampling;
+        let mut color = Vec4::ZERO;
+        for sy in 0..samples {
+            for sx in 0..samples {
+                let offset = (Vec2::new(sx as f32, sy as f32)
+                    + 0.5)
+                    / samples as f32;
+                let uv = (Vec2::new(x as f32, y as f32)
+                    + offset)
+                    / self.resolution();
+                color += self.shade(
+                    scene,
+                    center_sdf(uv, self.resolution()),
+                );
+            }
+        }
+        color / (samples * samples) as f32
+    }
+
+    pub fn render(&self, scene: &impl Sdf) -> RgbaImage {
+        RgbaImage::from_fn(self.width, self.height, |x, y| {
+            to_srgba8(self.pixel(scene, x, y))
+        })
+    }
+
+    pub fn save_png(
+        &self,
+        scene: &impl Sdf,
+        path: impl AsRef<Path>,
+    ) -> ImageResult<()> {
+        self.render(scene).save(path)
+    }
+}
+
+/// Encodes a linear shader colour the way the sRGB
+/// swapchain does.
+fn to_srgba8(color: Vec4) -> Rgba<u8> {
+    let encode = |c: f32| {
+        let c = c.clamp(0.0, 1.0);
+        let c = if c <= 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        };
+        (c * 255.0).round() as u8
+    };
+    Rgba([
+        encode(color.x),
+        encode(color.y),
+        encode(color.z),
+        (color.w.clamp(0.0, 1.0) * 255.0).round() as u8,
+    ])
+}
@@ -0,0 +1,267 @@
+//! Checks whether an SDF is an exact distance, only a bound
+//! on it, or not even that.
+//!
+//! An exact SDF has a gradient of length 1 everywhere, and
+//! the surface is exactly the distance away along it. A
+//! bound never overestimates, so its gradient is at most 1
+//! long, but it can be shorter (smooth unions) or point at
+//! a surface further away than the distance (the inside of
+//! a `min` union, like the rounded X). A gradient longer
+//! than 1 (a shape scaled without scaling the distance
+//! back) overestimates, and rays marched with it can step
+//! through the surface.
+
+use bevy::prelude::*;
+
+use crate::{
+    color_map::ColorMap,
+    sdf::{sd_box, smin, Sdf, Shape, GRADIENT_STEP},
+};
+
+/// What kind of distance an SDF returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exactness {
+    Exact,
+    /// Never more than the distance, so safe to march, but
+    /// sometimes less.
+    Bound,
+    /// Sometimes more than the distance.
+    NotLipschitz,
+}
+
+impl Exactness {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Exactness::Exact => "exact",
+            Exactness::Bound => "bound",
+            Exactness::NotLipschitz => "not Lipschitz-safe",
+        }
+    }
+}
+
+/// How closely an SDF has to keep to the distance, and
+/// where it's checked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExactnessCheck {
+    /// How far the gradient's length can be from 1, and the
+    /// distance from the one to the surface, before a
+    /// point is flagged.
+    pub tolerance: f32,
+    /// Samples along each side of the region.
+    pub samples: u32,
+    /// The fraction of points that can be flagged before
+    /// an SDF stops counting as exact, so the points just
+    /// off a crease, where the gradient is still estimated
+    /// across it, don't count.
+    pub max_flagged: f32,
+}
+
+impl Default for ExactnessCheck {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.01,
+            samples: 128,
+            max_flagged: 0.002,
+        }
+    }
+}
+
+/// What [`ExactnessCheck::check`] found.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExactnessReport {
+    pub exactness: Exactness,
+    /// The shortest and longest gradients.
+    pub min_gradient: f32,
+    pub max_gradient: f32,
+    /// The fraction of points flagged.
+    pub flagged: f32,
+    /// The fraction of points where the gradient is longer
+    /// than 1.
+    pub overestimated: f32,
+}
+
+impl ExactnessCheck {
+    /// How much longer than 1 the gradient of `sdf` is at
+    /// `point`: negative where it's shorter.
+    pub fn gradient_error(
+        sdf: &(impl Sdf + ?Sized),
+        point: Vec2,
+    ) -> f32 {
+        sdf.gradient(point).length() - 1.
+    }
+
+    /// Whether `point` is on a crease of `sdf`, where the
+    /// closest surface changes and the gradient can't be
+    /// estimated: the differences on either side of it
+    /// disagree by more than the tolerance.
+    pub fn on_crease(
+        &self,
+        sdf: &(impl Sdf + ?Sized),
+        point: Vec2,
+    ) -> bool {
+        let distance = sdf.distance(point);
+        let [forward, backward] = [1., -1.].map(|side| {
+            let step = |axis: Vec2| {
+                let offset = axis * side * GRADIENT_STEP;
+                (sdf.distance(point + offset) - distance)
+                    * side
+                    / GRADIENT_STEP
+            };
+            Vec2::new(step(Vec2::X), step(Vec2::Y))
+        });
+        forward.distance(backward) > self.tolerance
+    }
+
+    /// Whether `point` breaks the promise of an exact
+    /// distance: its gradient isn't 1 long, or the surface
+    /// along it is further away than the distance. Points
+    /// on a crease aren't flagged.
+    pub fn flags(
+        &self,
+        sdf: &(impl Sdf + ?Sized),
+        point: Vec2,
+    ) -> bool {
+        if self.on_crease(sdf, point) {
+            return false;
+        }
+        let distance = sdf.distance(point);
+        let surface = sdf.closest_point(point);
+        Self::gradient_error(sdf, point).abs()
+            > self.tolerance
+            || point.distance(surface) - distance.abs()
+                > self.tolerance
+    }
+
+    /// Samples the region between `min` and `max`, in scene
+    /// coordinates.
+    pub fn check(
+        &self,
+        sdf: &(impl Sdf + ?Sized),
+        min: Vec2,
+        max: Vec2,
+    ) -> ExactnessReport {
+        let samples = self.samples.max(1);
+        let spacing = (max - min) / samples as f32;
+        let mut min_gradient = f32::INFINITY;
+        let mut max_gradient: f32 = 0.;
+        let mut flagged = 0;
+        let mut overestimated = 0;
+        for y in 0..samples {
+            for x in 0..samples {
+                // x and y are offset differently so no
+                // sample lands on a diagonal, where the box's
+                // creases are
+                let point = min
+                    + Vec2::new(
+                        x as f32 + 0.5,
+                        y as f32 + 0.25,
+                    ) * spacing;
+                let length = sdf.gradient(point).length();
+                min_gradient = min_gradient.min(length);
+                max_gradient = max_gradient.max(length);
+                if self.flags(sdf, point) {
+                    flagged += 1;
+                }
+                if length > 1. + self.tolerance {
+                    overestimated += 1;
+                }
+            }
+        }
+        let fraction = |count: u32| {
+            count as f32 / (samples * samples) as f32
+        };
+        let flagged = fraction(flagged);
+        let overestimated = fraction(overestimated);
+        let exactness = if overestimated > self.max_flagged
+        {
+            Exactness::NotLipschitz
+        } else if flagged > self.max_flagged {
+            Exactness::Bound
+        } else {
+            Exactness::Exact
+        };
+        ExactnessReport {
+            exactness,
+            min_gradient,
+            max_gradient,
+            flagged,
+            overestimated,
+        }
+    }
+
+    /// The heatmap colour at `point`: grey where the gradient
+    /// is within the tolerance of 1, blue where it's
+    /// shorter and red where it's longer, with the surface
+    /// outlined in white. `width` is the distance one pixel
+    /// covers.
+    pub fn color(
+        &self,
+        sdf: &(impl Sdf + ?Sized),
+        point: Vec2,
+        width: f32,
+    ) -> Vec4 {
+        let error = Self::gradient_error(sdf, point);
+        let t = if error.abs() <= self.tolerance {
+            0.5
+        } else {
+            0.5 + error.clamp(-1., 1.) * 0.5
+        };
+        let rgb = ColorMap::Diverging
+            .ramp(t)
+            .expect("the diverging map has stops");
+        let outline = (1.5
+            - sdf.distance(point).abs() / width.max(1e-6))
+        .clamp(0., 1.);
+        rgb.lerp(Vec3::ONE, outline).extend(1.)
+    }
+}
+
+/// Every [`Shape`], and examples of the ways of combining
+/// SDFs that stop them being exact, to check with
+/// [`ExactnessCheck`].
+pub fn primitives() -> Vec<(&'static str, Box<dyn Sdf>)> {
+    let mut primitives: Vec<(&'static str, Box<dyn Sdf>)> =
+        Shape::ALL
+            .into_iter()
+            .map(|shape| {
+                (
+                    shape.name(),
+                    Box::new(shape) as Box<dyn Sdf>,
+                )
+            })
+            .collect();
+    let left = Vec2::new(-0.3, 0.);
+    let right = Vec2::new(0.3, 0.);
+    primitives.extend([
+        (
+            "union",
+            Box::new(move |p: Vec2| {
+                ((p - left).length() - 0.4)
+                    .min((p - right).length() - 0.4)
+            }) as Box<dyn Sdf>,
+        ),
+        (
+            "smooth union",
+            Box::new(move |p: Vec2| {
+                smin(
+                    (p - left).length() - 0.4,
+                    (p - right).length() - 0.4,
+                    0.2,
+                )
+            }),
+        ),
+        (
+            "scaled box",
+            Box::new(|p: Vec2| {
+                sd_box(p / 0.5, Vec2::splat(1.))
+            }),
+        ),
+        (
+            "scaled box, rescaled distance",
+            Box::new(|p: Vec2| {
+                sd_box(p / 0.5, Vec2::splat(1.)) * 0.5
+            }),
+        ),
+    ]);
+    primitives
+}
@@ -0,0 +1,307 @@
+//! A panel that explains a formula one step at a time,
+//! with the value of each step for the current mouse
+//! position.
+//!
+//! An explanation is a list of labelled rows, each with a
+//! colour and a closure computing its value. A row can
+//! also draw a gizmo, in the same colour, so adding a step
+//! is one [`FormulaPanel::row`] call:
+//!
+//! ```ignore
+//! FormulaPanel::default()
+//!     .row("abs(p)", Color::GREEN, |input| {
+//!         input.point().abs()
+//!     })
+//!     .gizmo(|input, gizmos, color| {
+//!         let abs = input.to_screen(input.point().abs());
+//!         gizmos.circle_2d(abs, 5., color);
+//!     })
+//! ```
+
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::{
+    bevy_plugin_shader2d::SdfMouse,
+    center_sdf, scene_to_world,
+    switcher::{ActiveExample, ExampleEntity},
+};
+
+/// What the rows are computed from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FormulaInput {
+    /// The [`SdfMouse`] position.
+    pub mouse: Vec2,
+    /// The window's size.
+    pub resolution: Vec2,
+}
+
+impl FormulaInput {
+    /// The mouse in the coordinates the SDFs are
+    /// evaluated in, as `center_sdf` computes them.
+    pub fn point(&self) -> Vec2 {
+        center_sdf(self.mouse, self.resolution)
+    }
+
+    /// Where a point in SDF coordinates is drawn by
+    /// gizmos, see [`scene_to_world`]. Being linear, it
+    /// maps offsets between points too.
+    pub fn to_screen(&self, point: Vec2) -> Vec2 {
+        scene_to_world(point, self.resolution)
+    }
+}
+
+/// A row's value, shown with its sign.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormulaValue {
+    Number(f32),
+    /// Shown as its `x` and `y` on separate lines.
+    Vector(Vec2),
+}
+
+impl From<f32> for FormulaValue {
+    fn from(value: f32) -> Self {
+        FormulaValue::Number(value)
+    }
+}
+
+impl From<Vec2> for FormulaValue {
+    fn from(value: Vec2) -> Self {
+        FormulaValue::Vector(value)
+    }
+}
+
+impl FormulaValue {
+    fn format(&self) -> String {
+        match self {
+            FormulaValue::Number(value) => {
+                format!("{value:+}")
+            }
+            FormulaValue::Vector(value) => format!(
+                "x: {:+}\n  y: {:+}",
+                value.x, value.y
+            ),
+        }
+    }
+}
+
+type ValueFn =
+    dyn Fn(&FormulaInput) -> FormulaValue + Send + Sync;
+type GizmoFn =
+    dyn Fn(&FormulaInput, &mut Gizmos, Color) + Send + Sync;
+
+struct FormulaRow {
+    label: &'static str,
+    color: Color,
+    value: Box<ValueFn>,
+    gizmo: Option<Box<GizmoFn>>,
+}
+
+/// The rows of an explanation, from top to bottom.
+#[derive(Default)]
+pub struct FormulaPanel {
+    rows: Vec<FormulaRow>,
+}
+
+impl FormulaPanel {
+    /// Adds a row showing `label` and `value`, in `color`.
+    pub fn row<V: Into<FormulaValue>>(
+        mut self,
+        label: &'static str,
+        color: Color,
+        value: impl Fn(&FormulaInput) -> V
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.rows.push(FormulaRow {
+            label,
+            color,
+            value: Box::new(move |input| {
+                value(input).into()
+            }),
+            gizmo: None,
+        });
+        self
+    }
+
+    /// Draws a gizmo for the last row, which gets the row's
+    /// colour.
+    pub fn gizmo(
+        mut self,
+        gizmo: impl Fn(&FormulaInput, &mut Gizmos, Color)
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        let row = self
+            .rows
+            .last_mut()
+            .expect("a gizmo belongs to a row");
+        row.gizmo = Some(Box::new(gizmo));
+        self
+    }
+
+    /// The label sections, followed by the empty ones the
+    /// values go in.
+    fn sections(&self) -> Vec<TextSection> {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(index, row)| {
+                let style = TextStyle {
+                    font_size: 20.0,
+                    color: row.color,
+                    ..default()
+                };
+                let separator =
+                    if index == 0 { "" } else { "\n\n" };
+                [
+                    TextSection::new(
+                        format!(
+                            "{separator}{}:\n  ",
+                            row.label
+                        ),
+                        style.clone(),
+                    ),
+                    TextSection::new("", style),
+                ]
+            })
+            .collect()
+    }
+}
+
+#[derive(Component)]
+struct FormulaText;
+
+/// Shows `panel` in the top left corner while `example`
+/// runs, and draws its gizmos.
+pub fn add_formula_panel(
+    app: &mut App,
+    example: &'static str,
+    panel: FormulaPanel,
+) {
+    let panel = Arc::new(panel);
+    let example = ActiveExample(example);
+    let sections = panel.clone();
+    let values = panel.clone();
+    app.add_systems(
+        OnEnter(example),
+        move |mut commands: Commands| {
+            spawn_panel(&mut commands, sections.sections())
+        },
+    )
+    .add_systems(
+        Update,
+        (
+            move |windows: Query<&Window>,
+                  mouse: Res<SdfMouse>,
+                  mut texts: Query<
+                &mut Text,
+                With<FormulaText>,
+            >| {
+                update_values(
+                    &values, windows, mouse, &mut texts,
+                )
+            },
+            move |windows: Query<&Window>,
+                  mouse: Res<SdfMouse>,
+                  mut gizmos: Gizmos| {
+                draw_gizmos(
+                    &panel,
+                    windows,
+                    mouse,
+                    &mut gizmos,
+                )
+            },
+        )
+            .run_if(in_state(example)),
+    );
+}
+
+fn spawn_panel(
+    commands: &mut Commands,
+    sections: Vec<TextSection>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(20.),
+                    top: Val::Px(20.),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: BackgroundColor(
+                    Color::BLACK,
+                ),
+                ..default()
+            },
+            ExampleEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    style: Style {
+                        margin: UiRect::all(Val::Px(20.)),
+                        ..default()
+                    },
+                    text: Text::from_sections(sections),
+                    ..default()
+                },
+                FormulaText,
+            ));
+        });
+}
+
+fn input(
+    windows: &Query<&Window>,
+    mouse: &SdfMouse,
+) -> Option<FormulaInput> {
+    let window = windows.get_single().ok()?;
+    Some(FormulaInput {
+        mouse: mouse.position,
+        resolution: Vec2::new(
+            window.width(),
+            window.height(),
+        ),
+    })
+}
+
+fn update_values(
+    panel: &FormulaPanel,
+    windows: Query<&Window>,
+    mouse: Res<SdfMouse>,
+    texts: &mut Query<&mut Text, With<FormulaText>>,
+) {
+    if !mouse.is_changed() {
+        return;
+    }
+    let Some(input) = input(&windows, &mouse) else {
+        return;
+    };
+    for mut text in texts.iter_mut() {
+        for (index, row) in panel.rows.iter().enumerate() {
+            text.sections[index * 2 + 1].value =
+                (row.value)(&input).format();
+        }
+    }
+}
+
+fn draw_gizmos(
+    panel: &FormulaPanel,
+    windows: Query<&Window>,
+    mouse: Res<SdfMouse>,
+    gizmos: &mut Gizmos,
+) {
+    let Some(input) = input(&windows, &mouse) else {
+        return;
+    };
+    for row in &panel.rows {
+        if let Some(gizmo) = &row.gizmo {
+            gizmo(&input, gizmos, row.color);
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! What the distance-as-gradient examples have in common:
+//! the shape they show, which the number keys switch
+//! between, and how distances are coloured: fading from
+//! the background to the inside and outside colours, or
+//! through a [colour map](crate::color_map).
+
+use bevy::prelude::*;
+
+use crate::{
+    color_map::{signed_position, ColorMap},
+    medial_axis::MedialAxis,
+    params::Params,
+    registry::{Param, ParamKind},
+    sdf::Shape,
+    switcher::ActiveExample,
+    vector_field::VectorField,
+};
+
+/// The shape the gradient shaders draw, which they receive
+/// in the `shape` uniform.
+#[derive(
+    Resource, Debug, Clone, Copy, PartialEq, Deref, DerefMut,
+)]
+pub struct CurrentShape(pub Shape);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientColors {
+    /// The colour at a distance of -1, fading to the
+    /// background at the surface.
+    pub inside: Color,
+    /// The colour at a distance of 1.
+    pub outside: Color,
+    /// The colour on the surface itself.
+    pub background: Color,
+    /// `Gradient` uses the colours above, the other maps
+    /// their own.
+    pub map: ColorMap,
+    /// The distance the colours reach their ends at.
+    pub range: f32,
+    /// Spreads out the small distances.
+    pub log_scale: bool,
+}
+
+impl Default for GradientColors {
+    fn default() -> Self {
+        Self {
+            inside: Color::rgb_linear(0., 0.855, 1.),
+            outside: Color::rgb_linear(1., 0.647, 0.),
+            background: Color::BLACK,
+            map: ColorMap::Gradient,
+            range: 1.,
+            log_scale: false,
+        }
+    }
+}
+
+impl GradientColors {
+    /// Reads the colours declared by [`params`].
+    pub fn from_params(params: &Params) -> Self {
+        Self {
+            inside: params.get("inside"),
+            outside: params.get("outside"),
+            background: params.get("background"),
+            map: ColorMap::from_name(
+                &params.get::<String>("color_map"),
+            )
+            .unwrap_or_default(),
+            range: params.get("distance_range"),
+            log_scale: params.get("log_scale"),
+        }
+    }
+
+    /// The linear colour the gradient shaders draw at
+    /// `distance`, as `distance_color` in `sdf.wgsl`.
+    pub fn color(&self, distance: f32) -> Vec4 {
+        let position = signed_position(
+            distance,
+            self.range,
+            self.log_scale,
+        );
+        if let Some(rgb) =
+            self.map.ramp(position * 0.5 + 0.5)
+        {
+            return rgb.extend(1.);
+        }
+        let linear = |color: Color| {
+            Vec4::from(color.as_linear_rgba_f32())
+        };
+        let end = if position >= 0. {
+            self.outside
+        } else {
+            self.inside
+        };
+        linear(self.background)
+            .lerp(linear(end), position.abs())
+    }
+}
+
+/// How a gradient example starts.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GradientConfig {
+    /// `None` starts with the example's own shape, or in
+    /// `single-sdf-distance-as-gradient` picks the shape
+    /// from the mouse position.
+    pub shape: Option<Shape>,
+    pub colors: GradientColors,
+    /// The arrows towards the surface, hidden until V
+    /// is pressed unless enabled.
+    pub vector_field: VectorField,
+    /// The shape's skeleton, hidden until M is pressed
+    /// unless enabled.
+    pub medial_axis: MedialAxis,
+}
+
+impl GradientConfig {
+    /// Reads the parameters declared by [`params`].
+    pub fn from_params(params: &Params) -> Self {
+        Self {
+            shape: Shape::from_name(
+                &params.get::<String>("shape"),
+            ),
+            colors: GradientColors::from_params(params),
+            vector_field: VectorField::from_params(params),
+            medial_axis: MedialAxis::from_params(params),
+        }
+    }
+}
+
+/// The parameters every gradient example accepts. `shapes`
+/// are the accepted values for `shape=`, usually
+/// [`Shape::NAMES`].
+pub const fn params(
+    shapes: &'static [&'static str],
+    default_shape: &'static str,
+) -> [Param; 12] {
+    [
+        Param {
+            name: "shape",
+            kind: ParamKind::Enum(shapes),
+            default: default_shape,
+            description: "The shape to start with. The number keys switch shapes.",
+        },
+        // the hex equivalents of the default colours
+        Param {
+            name: "inside",
+            kind: ParamKind::Color,
+            default: "00eeff",
+            description: "The colour inside the shape.",
+        },
+        Param {
+            name: "outside",
+            kind: ParamKind::Color,
+            default: "ffd200",
+            description: "The colour outside the shape.",
+        },
+        Param {
+            name: "background",
+            kind: ParamKind::Color,
+            default: "000000",
+            description: "The colour the gradients fade to at the surface.",
+        },
+        Param {
+            name: "color_map",
+            kind: ParamKind::Enum(&ColorMap::NAMES),
+            default: "gradient",
+            description: "The inside, outside and background colours, or a colour map from inside to outside. cividis stays readable with colour blindness.",
+        },
+        Param {
+            name: "distance_range",
+            kind: ParamKind::Range {
+                min: 0.1,
+                max: 4.,
+            },
+            default: "1",
+            description: "The distance the colours reach their ends at.",
+        },
+        Param {
+            name: "log_scale",
+            kind: ParamKind::Bool,
+            default: "false",
+            description: "Spreads the colours out logarithmically, to tell small distances apart.",
+        },
+        Param {
+            name: "vector_field",
+            kind: ParamKind::Bool,
+            default: "false",
+            description: "Draws arrows towards the closest surface on a grid. V turns them on and off.",
+        },
+        Param {
+            name: "vector_field_cells",
+            kind: ParamKind::Int {
+                min: 2,
+                max: 64,
+            },
+            default: "16",
+            description: "How many arrows fit across the window's shorter side.",
+        },
+        Param {
+            name: "vector_field_scale",
+            kind: ParamKind::Range {
+                min: 0.05,
+                max: 1.,
+            },
+            default: "0.25",
+            description: "The length of an arrow over the distance at its start. 1 ends the arrows on the surface.",
+        },
+        Param {
+            name: "medial_axis",
+            kind: ParamKind::Bool,
+            default: "false",
+            description: "Draws the shape's skeleton, and the largest circle inside the shape closest to the mouse. M turns it on and off.",
+        },
+        Param {
+            name: "medial_axis_cells",
+            kind: ParamKind::Int {
+                min: 16,
+                max: 256,
+            },
+            default: "96",
+            description: "How many grid cells across the window's shorter side the skeleton is looked for on.",
+        },
+    ]
+}
+
+/// The shape a number key picks. The numbers are the shape
+/// ids from `sdf.wgsl`, so 1 is the circle and 5 the cool S.
+pub fn shape_for_key(key: KeyCode) -> Option<Shape> {
+    match key {
+        KeyCode::Key1 | KeyCode::Numpad1 => {
+            Some(Shape::Circle)
+        }
+        KeyCode::Key2 | KeyCode::Numpad2 => {
+            Some(Shape::Box)
+        }
+        KeyCode::Key3 | KeyCode::Numpad3 => {
+            Some(Shape::EquilateralTriangle)
+        }
+        KeyCode::Key4 | KeyCode::Numpad4 => Some(Shape::X),
+        KeyCode::Key5 | KeyCode::Numpad5 => {
+            Some(Shape::CoolS)
+        }
+        _ => None,
+    }
+}
+
+pub fn switch_shape_with_keys(
+    keys: Res<Input<KeyCode>>,
+    mut current_shape: ResMut<CurrentShape>,
+) {
+    if let Some(shape) = keys
+        .get_just_pressed()
+        .filter_map(|key| shape_for_key(*key))
+        .last()
+    {
+        current_shape.0 = shape;
+    }
+}
+
+/// Adds [`CurrentShape`], starting at `shape`, while
+/// `example` runs, and lets the number keys switch it.
+pub fn add_current_shape(
+    app: &mut App,
+    example: &'static str,
+    shape: Shape,
+) {
+    let example = ActiveExample(example);
+    app.add_systems(
+        OnEnter(example),
+        move |mut commands: Commands| {
+            commands.insert_resource(CurrentShape(shape))
+        },
+    )
+    .add_systems(
+        OnExit(example),
+        |mut commands: Commands| {
+            commands.remove_resource::<CurrentShape>()
+        },
+    )
+    .add_systems(
+        Update,
+        switch_shape_with_keys.run_if(in_state(example)),
+    );
+}
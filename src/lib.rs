@@ -1,47 +1,76 @@
-use std::ops::Deref;
-
-use bevy::{prelude::*, utils::HashMap};
-mod apps;
+use bevy::prelude::*;
+use bevy_plugin_shader2d::SdfWindowPlugin;
+use link::{ExampleParams, LinkPlugin};
+use params::{ParamError, Params};
+use switcher::{ActiveExample, ExampleSwitcherPlugin};
+pub mod apps;
 pub mod bevy_plugin_shader2d;
+pub mod color_map;
+pub mod contours;
+pub mod cpu_renderer;
+pub mod exactness;
+pub mod formula_panel;
+pub mod gradient;
+pub mod link;
+pub mod marching_squares;
+pub mod material_params;
+pub mod medial_axis;
+pub mod mesher;
+pub mod params;
+pub mod raymarch;
+pub mod registry;
+pub mod sdf;
+pub mod storyboard;
+pub mod switcher;
+pub mod vector_field;
+pub mod voronoi;
 
+/// Runs the example picked by the `example` parameter,
+/// after checking the rest of the parameters against the
+/// ones it declares in [`registry::EXAMPLES`].
+///
+/// Every other example is added too, with its default
+/// parameters, so the menu can switch to it.
 pub fn examples(
-    example: String,
-    params: HashMap<String, String>,
-) {
-    match example.deref() {
-        "single-sdf-distance-as-gradient" => {
-            apps::single_sdf_distance_as_gradient::app();
-        }
-        "single-sdf-distance-as-gradient-with-mouse" => {
-            apps::single_sdf_distance_as_gradient_with_mouse::app();
-        }
-        "single-sdf-distance-as-gradient-with-mouse-and-inner-ray" => {
-            apps::single_sdf_distance_as_gradient_with_mouse_and_inner_ray::app();
-}
-        "single-sdf-distance-as-gradient-with-abs-mouse" => {
-            apps::single_sdf_distance_as_gradient_with_abs_mouse::app();
-}
-        "single-sdf-distance-as-gradient-with-algorithm" => {
-            apps::single_sdf_distance_as_gradient_with_algorithm::app();
+    params: Params,
+) -> Result<(), Vec<ParamError>> {
+    examples_app(params)?.run();
+    Ok(())
 }
-        "single-sdf-distance-as-circle" => {
-use            apps::single_sdf_distance_as_circle::Lights;
-let lights = match           params.get("lights").cloned().unwrap_or("on".to_string()).deref(){
 
-        "on" => Lights::On,
-        "off" => Lights::Off,
-        _ => Lights::On
-    }
-;
-            apps::single_sdf_distance_as_circle::app(lights);
-}
-        "single-sdf-distance-as-border" => {
-            apps::single_sdf_distance_as_border::app();
-}
-        _ => {
-            panic!("example doesn't exist");
-        }
+/// The app [`examples`] runs, to add more plugins to it
+/// first.
+pub fn examples_app(
+    params: Params,
+) -> Result<App, Vec<ParamError>> {
+    let example =
+        params.example().map_err(|error| vec![error])?;
+    let params = params.validate(example)?;
+
+    let mut app = App::new();
+    app.add_plugins((
+        SdfWindowPlugin,
+        ExampleSwitcherPlugin,
+        LinkPlugin {
+            params: params.clone(),
+        },
+    ));
+    // after the plugins, so logging is set up
+    info!("example is: {}", example.id);
+    let mut example_params = ExampleParams::default();
+    for other in registry::EXAMPLES {
+        let other_params = if other.id == example.id {
+            params.clone()
+        } else {
+            Params::defaults(other)
+        };
+        (other.add)(&mut app, &other_params);
+        example_params.0.insert(other.id, other_params);
     }
+    app.insert_resource(example_params).insert_resource(
+        State::new(ActiveExample(example.id)),
+    );
+    Ok(app)
 }
 
 pub fn center_sdf(uv: Vec2, width_height: Vec2) -> Vec2 {
@@ -72,3 +101,15 @@ pub fn center_sdf(uv: Vec2, width_height: Vec2) -> Vec2 {
 
     coord
 }
+
+/// Where a point in [`center_sdf`] coordinates is drawn by
+/// gizmos, for the default camera: the scene's y points
+/// down like the uvs, the world's up.
+pub fn scene_to_world(
+    coord: Vec2,
+    width_height: Vec2,
+) -> Vec2 {
+    Vec2::new(coord.x, -coord.y)
+        * width_height.min_element()
+        / 2.0
+}
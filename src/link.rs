@@ -0,0 +1,250 @@
+//! The running example's view as a link: its parameters,
+//! including the shape and any values changed in the
+//! [material panel](crate::material_params), the pinned
+//! mouse position, the camera and the
+//! [contours](crate::contours).
+//!
+//! On the web the link replaces the page's url as the view
+//! changes, so the address bar always holds a link to what's
+//! on screen. Natively it's written to [`LINK_FILE`], which
+//! `--resume` reopens.
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    bevy_plugin_shader2d::{
+        set_camera_center, CompareCamera, SdfMouse,
+    },
+    contours::Contours,
+    gradient::CurrentShape,
+    material_params::MaterialPanel,
+    params::Params,
+    registry::VIEW_PARAMS,
+    switcher::ActiveExample,
+};
+
+/// Where the native build writes the link.
+pub const LINK_FILE: &str = "yt-raymarch-2d-link.txt";
+
+/// The current parameters of every example, kept while
+/// another example runs.
+#[derive(Resource, Default)]
+pub struct ExampleParams(pub HashMap<&'static str, Params>);
+
+/// Restores the view given by the [view
+/// parameters](crate::registry::VIEW_PARAMS) in `params`,
+/// then keeps the link up to date.
+pub struct LinkPlugin {
+    /// The parameters the app was started with.
+    pub params: Params,
+}
+
+impl Plugin for LinkPlugin {
+    fn build(&self, app: &mut App) {
+        if self.params.contains("mouse") {
+            app.insert_resource(SdfMouse {
+                position: self.params.get("mouse"),
+                pinned: true,
+            });
+        }
+        let zoom: f32 = self.params.get("zoom");
+        let center: Vec2 = self.params.get("center");
+        app.insert_resource(Contours::from_params(
+            &self.params,
+        ));
+        app.init_resource::<ExampleParams>()
+            .add_systems(
+                PostStartup,
+                move |windows: Query<&Window>,
+                      cameras: Query<
+                    (
+                        &mut Transform,
+                        &mut OrthographicProjection,
+                    ),
+                    Without<CompareCamera>,
+                >| {
+                    move_camera(
+                        windows, cameras, zoom, center,
+                    )
+                },
+            )
+            .add_systems(
+                Update,
+                (record_panel, record_shape, write_link)
+                    .chain(),
+            );
+    }
+}
+
+fn move_camera(
+    windows: Query<&Window>,
+    mut cameras: Query<
+        (&mut Transform, &mut OrthographicProjection),
+        Without<CompareCamera>,
+    >,
+    zoom: f32,
+    center: Vec2,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let half_window =
+        Vec2::new(window.width(), window.height()) / 2.;
+    for (mut transform, mut projection) in
+        cameras.iter_mut()
+    {
+        projection.scale = 1. / zoom;
+        set_camera_center(
+            &mut transform,
+            window,
+            projection.scale,
+            center * half_window,
+        );
+    }
+}
+
+/// Copies the values changed in the panel.
+fn record_panel(
+    panel: Option<Res<MaterialPanel>>,
+    mut example_params: ResMut<ExampleParams>,
+) {
+    let Some(panel) = panel else {
+        return;
+    };
+    if !panel.is_changed() {
+        return;
+    }
+    let Some(params) =
+        example_params.0.get_mut(panel.example)
+    else {
+        return;
+    };
+    for name in panel.fields {
+        params.insert(
+            name.to_string(),
+            panel.params.value(name).map(str::to_string),
+        );
+    }
+}
+
+fn record_shape(
+    shape: Option<Res<CurrentShape>>,
+    active: Res<State<ActiveExample>>,
+    mut example_params: ResMut<ExampleParams>,
+) {
+    let Some(shape) = shape else {
+        return;
+    };
+    // only record the shape once it's been switched, so
+    // `shape=mouse` stays in the link until then
+    if !shape.is_changed() || shape.is_added() {
+        return;
+    }
+    let Some(params) = example_params.0.get_mut(active.0)
+    else {
+        return;
+    };
+    params.insert(
+        "shape".to_string(),
+        Some(shape.name().to_string()),
+    );
+}
+
+/// Builds the link from the view, and writes it out when
+/// it changes.
+fn write_link(
+    active: Res<State<ActiveExample>>,
+    example_params: Res<ExampleParams>,
+    mouse: Res<SdfMouse>,
+    contours: Res<Contours>,
+    windows: Query<&Window>,
+    cameras: Query<
+        (&Transform, &OrthographicProjection),
+        (With<Camera2d>, Without<CompareCamera>),
+    >,
+    mut last_link: Local<String>,
+) {
+    let Some(params) = example_params.0.get(active.0)
+    else {
+        return;
+    };
+    // the view comes from the mouse, camera and contours
+    // instead
+    let mut params = params.clone();
+    for param in VIEW_PARAMS {
+        params.remove(param.name);
+    }
+    if mouse.pinned {
+        params.insert(
+            "mouse".to_string(),
+            Some(format!(
+                "{:.4},{:.4}",
+                mouse.position.x, mouse.position.y
+            )),
+        );
+    }
+    if let (Ok(window), Ok((transform, projection))) =
+        (windows.get_single(), cameras.get_single())
+    {
+        let half_window =
+            Vec2::new(window.width(), window.height()) / 2.;
+        let center =
+            transform.translation.truncate() / half_window;
+        if projection.scale != 1. {
+            params.insert(
+                "zoom".to_string(),
+                Some(format!(
+                    "{:.3}",
+                    1. / projection.scale
+                )),
+            );
+        }
+        if center != Vec2::ZERO {
+            params.insert(
+                "center".to_string(),
+                Some(format!(
+                    "{:.4},{:.4}",
+                    center.x, center.y
+                )),
+            );
+        }
+    }
+    for (name, value) in contours.to_params() {
+        params.insert(name.to_string(), Some(value));
+    }
+
+    let link = params.to_query(active.0);
+    if *last_link != link {
+        write(&link);
+        *last_link = link;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write(query: &str) {
+    let Some(history) = web_sys::window()
+        .and_then(|window| window.history().ok())
+    else {
+        return;
+    };
+    if let Err(error) = history.replace_state_with_url(
+        &wasm_bindgen::JsValue::NULL,
+        "",
+        Some(query),
+    ) {
+        warn!("couldn't update the url: {error:?}");
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(query: &str) {
+    if let Err(error) = std::fs::write(
+        LINK_FILE,
+        format!(
+            "{}{query}\n",
+            crate::registry::LIVE_DEMO_URL
+        ),
+    ) {
+        warn!("couldn't write {LINK_FILE}: {error}");
+    }
+}
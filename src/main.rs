@@ -1,27 +1,192 @@
-use bevy::utils::HashMap;
+use yt_raymarch_2d::{
+    params::{ParamError, Params},
+    registry,
+};
 
+/// Reads the example and its parameters from the page's
+/// query string, eg: `?example=single-sdf-distance-as-circle&lights=off`.
+/// Shows the list of examples if none was picked, and the
+/// errors if the parameters are wrong.
+#[cfg(target_arch = "wasm32")]
 fn main() {
-    // panic!("here");
-    let querystring = web_sys::window()
+    let search = web_sys::window()
         .expect("expect a window")
         .location()
         .search()
-        .ok();
-    let query = querystring.map(|s| {
-        s.trim_start_matches("?")
-            .split("&")
-            .map(|keyvalue| {
-                let mut it = keyvalue.split("=");
-                let key = it.next().unwrap();
-                let value = it.next().unwrap();
-                (key.to_string(), value.to_string())
-            })
-            .collect::<HashMap<String, String>>()
-    }).expect("should be at least an empty hashmap at this point");
+        .unwrap_or_default();
+    let result = Params::parse_query(&search)
+        .map_err(|error| vec![error])
+        .and_then(yt_raymarch_2d::examples);
+
+    match result {
+        Ok(()) => {}
+        Err(errors)
+            if errors == [ParamError::NoExample] =>
+        {
+            set_body(&registry::index_html())
+        }
+        Err(errors) => set_body(
+            &yt_raymarch_2d::params::errors_html(&errors),
+        ),
+    }
+}
+
+/// Replaces the page's content, for the index and error
+/// pages.
+#[cfg(target_arch = "wasm32")]
+fn set_body(html: &str) {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.body())
+        .expect("expect a document body")
+        .set_inner_html(html);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+const USAGE: &str = "\
+usage: yt-raymarch-2d <example> [key=value | flag ...]
+       yt-raymarch-2d --resume [link file]
+       yt-raymarch-2d --storyboard <file.ron>
+       yt-raymarch-2d --list
+       yt-raymarch-2d --help
 
-    let Some(example) = query.get("example") else {
-        panic!("no example!");
+Runs one of the example apps in a native window. Parameters
+are the same ones the web build reads from the query string,
+eg: yt-raymarch-2d single-sdf-distance-as-circle lights=off
+
+The link to the current view is kept in yt-raymarch-2d-link.txt,
+and --resume reopens it (or any other live demo link).
+
+--storyboard plays a scripted take, see src/storyboard.rs.";
+
+/// Reads the example and its parameters from the command
+/// line, eg: `single-sdf-distance-as-circle lights=off`
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let example = match args.next().as_deref() {
+        Some("--help" | "-h") => {
+            println!("{USAGE}");
+            return;
+        }
+        Some("--list") => {
+            for example in registry::EXAMPLES {
+                println!(
+                    "{}\n    {}",
+                    example.id, example.title
+                );
+                for param in example.params {
+                    println!(
+                        "    {}={} (default {})",
+                        param.name,
+                        param.kind.describe(),
+                        param.default
+                    );
+                }
+            }
+            println!("every example also takes");
+            for param in registry::VIEW_PARAMS {
+                println!(
+                    "    {}={}",
+                    param.name,
+                    param.kind.describe()
+                );
+            }
+            return;
+        }
+        Some("--resume") => {
+            let file = args.next().unwrap_or_else(|| {
+                yt_raymarch_2d::link::LINK_FILE.to_string()
+            });
+            run(resume(&file));
+            return;
+        }
+        Some("--storyboard") => {
+            let Some(file) = args.next() else {
+                eprintln!("{USAGE}");
+                std::process::exit(2);
+            };
+            play(&file);
+            return;
+        }
+        Some(example) => example.to_string(),
+        None => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
     };
-    println!("example is: {example}");
-    yt_raymarch_2d::examples(example.clone(), query)
+
+    let mut params = Params::from_args(args);
+    params.insert("example".to_string(), Some(example));
+    run(Ok(params));
+}
+
+/// Reads the parameters from a link, or a file holding
+/// one.
+#[cfg(not(target_arch = "wasm32"))]
+fn resume(file: &str) -> Result<Params, ParamError> {
+    let link = std::fs::read_to_string(file)
+        .unwrap_or_else(|_| file.to_string());
+    let query = link
+        .trim()
+        .split_once('?')
+        .map_or("", |(_, query)| query);
+    Params::parse_query(query)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn run(params: Result<Params, ParamError>) {
+    let result = params
+        .map_err(|error| vec![error])
+        .and_then(yt_raymarch_2d::examples);
+    if let Err(errors) = result {
+        for error in errors.iter() {
+            eprintln!("error: {error}");
+        }
+        if let [ParamError::UnknownExample(_)] =
+            errors.as_slice()
+        {
+            eprintln!(
+                "see --list for the available examples"
+            );
+        }
+        std::process::exit(2);
+    }
+}
+
+/// Plays the storyboard in `file`.
+#[cfg(not(target_arch = "wasm32"))]
+fn play(file: &str) {
+    use yt_raymarch_2d::storyboard::{
+        Storyboard, StoryboardPlugin,
+    };
+
+    let storyboard = std::fs::read_to_string(file)
+        .map_err(|error| error.to_string())
+        .and_then(|text| {
+            Storyboard::from_ron(&text)
+                .map_err(|error| error.to_string())
+        });
+    let storyboard = match storyboard {
+        Ok(storyboard) => storyboard,
+        Err(error) => {
+            eprintln!("error: {file}: {error}");
+            std::process::exit(2);
+        }
+    };
+    match yt_raymarch_2d::examples_app(storyboard.params())
+    {
+        Ok(mut app) => {
+            app.add_plugins(StoryboardPlugin {
+                storyboard,
+            })
+            .run();
+        }
+        Err(errors) => {
+            for error in errors.iter() {
+                eprintln!("error: {error}");
+            }
+            std::process::exit(2);
+        }
+    }
 }
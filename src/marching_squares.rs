@@ -0,0 +1,245 @@
+//! Contours of any [`Sdf`] by marching squares: the
+//! surface, or the line at any other distance, as
+//! polylines.
+//!
+//! An [`Outline`] draws a scene's shapes with gizmos from
+//! its SDF alone, so a scene doesn't have to draw its own
+//! outlines.
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::sdf::Sdf;
+
+/// How finely contours are traced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarchingSquares {
+    /// How many grid cells fit across the region's shorter
+    /// side.
+    pub cells: u32,
+}
+
+impl Default for MarchingSquares {
+    fn default() -> Self {
+        Self { cells: 160 }
+    }
+}
+
+impl MarchingSquares {
+    /// The line where `sdf` is `iso` between `min` and
+    /// `max`, as polylines. A closed loop ends with its
+    /// first point; the others end at the edge of the
+    /// region.
+    pub fn extract(
+        &self,
+        sdf: &(impl Sdf + ?Sized),
+        iso: f32,
+        min: Vec2,
+        max: Vec2,
+    ) -> Vec<Vec<Vec2>> {
+        let size = max - min;
+        let spacing =
+            size.min_element() / self.cells.max(1) as f32;
+        let count = (size / spacing).ceil().as_uvec2();
+        let (nx, ny) = (count.x + 1, count.y + 1);
+        let at = |x: u32, y: u32| {
+            min + Vec2::new(x as f32, y as f32) * spacing
+        };
+        let values: Vec<f32> = (0..ny)
+            .flat_map(|y| (0..nx).map(move |x| (x, y)))
+            .map(|(x, y)| sdf.distance(at(x, y)) - iso)
+            .collect();
+        let value =
+            |x: u32, y: u32| values[(y * nx + x) as usize];
+
+        let mut chains = Chains::default();
+        // where the contour crosses the edge from `(x, y)`
+        // to the next grid point along `step`, keyed by the
+        // edge so both cells sharing it share the point
+        let mut crossings = HashMap::new();
+        let mut crossing =
+            |chains: &mut Chains,
+             x: u32,
+             y: u32,
+             step: UVec2| {
+                *crossings
+                    .entry((x, y, step.x))
+                    .or_insert_with(|| {
+                        let (va, vb) = (
+                            value(x, y),
+                            value(x + step.x, y + step.y),
+                        );
+                        let t = va / (va - vb);
+                        chains.add(at(x, y).lerp(
+                            at(x + step.x, y + step.y),
+                            t,
+                        ))
+                    })
+            };
+        for y in 0..ny - 1 {
+            for x in 0..nx - 1 {
+                let corners = [
+                    (x, y),
+                    (x + 1, y),
+                    (x + 1, y + 1),
+                    (x, y + 1),
+                ];
+                let inside =
+                    corners.map(|(x, y)| value(x, y) < 0.);
+                // edge k runs from corner k to the next one
+                let edges = [
+                    (x, y, UVec2::X),
+                    (x + 1, y, UVec2::Y),
+                    (x, y + 1, UVec2::X),
+                    (x, y, UVec2::Y),
+                ];
+                let crossed: Vec<usize> = (0..4)
+                    .filter(|k| {
+                        inside[*k] != inside[(k + 1) % 4]
+                    })
+                    .collect();
+                let mut point =
+                    |chains: &mut Chains, edge: usize| {
+                        let (x, y, step) = edges[edge];
+                        crossing(chains, x, y, step)
+                    };
+                match crossed.as_slice() {
+                    [a, b] => {
+                        let a = point(&mut chains, *a);
+                        let b = point(&mut chains, *b);
+                        chains.connect(a, b);
+                    }
+                    [_, _, _, _] => {
+                        // a saddle: cut off the two corners
+                        // on the other side from the middle
+                        // of the cell
+                        let middle = sdf.distance(
+                            at(x, y) + spacing / 2.,
+                        ) < iso;
+                        for k in 0..4 {
+                            if inside[k] != middle {
+                                let a = point(
+                                    &mut chains,
+                                    (k + 3) % 4,
+                                );
+                                let b =
+                                    point(&mut chains, k);
+                                chains.connect(a, b);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        chains.polylines()
+    }
+}
+
+/// The surface of a scene as polylines, traced again only
+/// when the region it covers changes. Keep one in a
+/// `Local` for each scene and draw it every frame.
+#[derive(Debug, Default, Clone)]
+pub struct Outline {
+    /// The `min` and `max` last traced.
+    region: Option<(Vec2, Vec2)>,
+    polylines: Vec<Vec<Vec2>>,
+}
+
+impl Outline {
+    /// The surface of `sdf` between `min` and `max`,
+    /// traced with `marching` unless that region was the
+    /// last one traced.
+    pub fn trace(
+        &mut self,
+        marching: &MarchingSquares,
+        sdf: &(impl Sdf + ?Sized),
+        min: Vec2,
+        max: Vec2,
+    ) -> &[Vec<Vec2>] {
+        if self.region != Some((min, max)) {
+            self.polylines =
+                marching.extract(sdf, 0., min, max);
+            self.region = Some((min, max));
+        }
+        &self.polylines
+    }
+
+    /// Draws the surface of `sdf` between `min` and `max`
+    /// in `color`, in the same coordinates as the gizmos.
+    pub fn draw(
+        &mut self,
+        marching: &MarchingSquares,
+        gizmos: &mut Gizmos,
+        sdf: &(impl Sdf + ?Sized),
+        min: Vec2,
+        max: Vec2,
+        color: Color,
+    ) {
+        for polyline in self.trace(marching, sdf, min, max)
+        {
+            gizmos.linestrip_2d(
+                polyline.iter().copied(),
+                color,
+            );
+        }
+    }
+}
+
+/// Contour points and the segments between them. Every
+/// point is on at most two segments.
+#[derive(Default)]
+struct Chains {
+    points: Vec<Vec2>,
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl Chains {
+    fn add(&mut self, point: Vec2) -> usize {
+        self.points.push(point);
+        self.neighbours.push(vec![]);
+        self.points.len() - 1
+    }
+
+    fn connect(&mut self, a: usize, b: usize) {
+        self.neighbours[a].push(b);
+        self.neighbours[b].push(a);
+    }
+
+    /// Follows the chains from their ends at the edge of
+    /// the region, then around the loops left.
+    fn polylines(&self) -> Vec<Vec<Vec2>> {
+        let mut visited = vec![false; self.points.len()];
+        let mut polylines = vec![];
+        let starts = (0..self.points.len())
+            .filter(|index| {
+                self.neighbours[*index].len() < 2
+            })
+            .chain(0..self.points.len());
+        for start in starts {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut polyline = vec![self.points[start]];
+            let (mut previous, mut current) =
+                (start, start);
+            while let Some(&next) = self.neighbours[current]
+                .iter()
+                .find(|n| **n != previous && **n != current)
+            {
+                polyline.push(self.points[next]);
+                if visited[next] {
+                    // back at the start of a loop
+                    break;
+                }
+                visited[next] = true;
+                previous = current;
+                current = next;
+            }
+            if polyline.len() > 1 {
+                polylines.push(polyline);
+            }
+        }
+        polylines
+    }
+}
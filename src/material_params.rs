@@ -0,0 +1,683 @@
+//! Material fields set from the example's parameters, and a
+//! panel in the top right corner to tweak them while the
+//! example runs.
+//!
+//! A parameter sets the material field with the same name,
+//! found through `Reflect`, so a new tunable only needs the
+//! field and a [`Param`](crate::registry::Param)
+//! declaration:
+//!
+//! * `F32` and `Range` parameters set `f32` fields. Ranges
+//!   get a slider.
+//! * `Int` parameters set `f32` or `u32` fields and get a
+//!   slider that stops at whole numbers.
+//! * `Color` parameters set `Color` fields and get a slider
+//!   for each of red, green and blue.
+//! * `Enum` and `Bool` parameters set an `f32` or `u32`
+//!   field to the index of their value (`false` is 0,
+//!   `true` is 1) and get a dropdown.
+//! * `Vec2` parameters set `Vec2` fields.
+
+use std::marker::PhantomData;
+
+use bevy::{
+    prelude::*, reflect::Struct, sprite::Material2d,
+    ui::RelativeCursorPosition,
+};
+
+use crate::{
+    bevy_plugin_shader2d::UserShader,
+    params::Params,
+    registry::ParamKind,
+    switcher::{ActiveExample, ExampleEntity},
+};
+
+/// Sets the `fields` of the `M` material `example` draws
+/// from `params`, and shows them in the panel.
+///
+/// Add it after the plugin that adds the example's
+/// [`Shader2dWindowPlugin`](crate::bevy_plugin_shader2d::Shader2dWindowPlugin).
+pub struct MaterialParamsPlugin<M> {
+    pub example: &'static str,
+    pub params: Params,
+    /// The parameters that set material fields.
+    pub fields: &'static [&'static str],
+    material: PhantomData<M>,
+}
+
+impl<M> MaterialParamsPlugin<M> {
+    pub fn new(
+        example: &'static str,
+        params: &Params,
+        fields: &'static [&'static str],
+    ) -> Self {
+        Self {
+            example,
+            params: params.clone(),
+            fields,
+            material: PhantomData,
+        }
+    }
+}
+
+impl<M: Material2d + Struct> Plugin
+    for MaterialParamsPlugin<M>
+{
+    fn build(&self, app: &mut App) {
+        let mut shader = app
+            .world
+            .get_resource_mut::<UserShader<M>>()
+            .expect("expect the example's Shader2dWindowPlugin to be added first");
+        for name in self.fields {
+            set_field(&mut shader.0, &self.params, name);
+        }
+
+        if !app.is_plugin_added::<MaterialPanelPlugin>() {
+            app.add_plugins(MaterialPanelPlugin);
+        }
+        let example = ActiveExample(self.example);
+        app.insert_resource(MaterialParams::<M> {
+            example: self.example,
+            params: self.params.clone(),
+            fields: self.fields,
+            material: PhantomData,
+        })
+        .add_systems(OnEnter(example), open_panel::<M>)
+        .add_systems(OnExit(example), close_panel)
+        .add_systems(
+            Update,
+            apply_panel::<M>.run_if(in_state(example)),
+        );
+    }
+}
+
+/// The current values of an example's material
+/// parameters, kept while another example runs.
+#[derive(Resource)]
+struct MaterialParams<M> {
+    example: &'static str,
+    params: Params,
+    fields: &'static [&'static str],
+    material: PhantomData<M>,
+}
+
+/// The values shown in the panel of the running example.
+/// The panel's controls change these, and the example's
+/// [`MaterialParamsPlugin`] copies them to its materials.
+#[derive(Resource)]
+pub struct MaterialPanel {
+    pub example: &'static str,
+    pub params: Params,
+    pub fields: &'static [&'static str],
+}
+
+/// Sets the field of `material` called `name` to the value
+/// of the parameter with the same name.
+///
+/// Panics if there is no such field, or if the parameter's
+/// kind can't set a field of its type.
+pub fn set_field(
+    material: &mut dyn Struct,
+    params: &Params,
+    name: &str,
+) {
+    let Some(field) = material.field_mut(name) else {
+        panic!("the material has no field called `{name}`");
+    };
+    let set = match &params.declared(name).kind {
+        ParamKind::F32 | ParamKind::Range { .. } => {
+            set_number(field, params.get::<f32>(name))
+        }
+        ParamKind::Int { .. } => set_number(
+            field,
+            params.get::<u32>(name) as f32,
+        ),
+        ParamKind::Bool => set_number(
+            field,
+            params.get::<bool>(name) as u32 as f32,
+        ),
+        ParamKind::Enum(values) => {
+            let index = values
+                .iter()
+                .position(|value| {
+                    Some(*value) == params.value(name)
+                })
+                .expect("expect a validated enum value");
+            set_number(field, index as f32)
+        }
+        ParamKind::Color => field
+            .downcast_mut::<Color>()
+            .map(|color| *color = params.get(name)),
+        ParamKind::Vec2 => field
+            .downcast_mut::<Vec2>()
+            .map(|vec| *vec = params.get(name)),
+        ParamKind::Path => None,
+    };
+    if set.is_none() {
+        panic!(
+            "`{name}` can't be set from a {:?} parameter",
+            params.declared(name).kind
+        );
+    }
+}
+
+fn set_number(
+    field: &mut dyn Reflect,
+    number: f32,
+) -> Option<()> {
+    if let Some(field) = field.downcast_mut::<f32>() {
+        *field = number;
+    } else {
+        *field.downcast_mut::<u32>()? = number as u32;
+    }
+    Some(())
+}
+
+fn open_panel<M: Material2d + Struct>(
+    mut commands: Commands,
+    state: Res<MaterialParams<M>>,
+) {
+    commands.insert_resource(MaterialPanel {
+        example: state.example,
+        params: state.params.clone(),
+        fields: state.fields,
+    });
+    spawn_panel(&mut commands, &state.params, state.fields);
+}
+
+fn close_panel(mut commands: Commands) {
+    commands.remove_resource::<MaterialPanel>();
+}
+
+fn apply_panel<M: Material2d + Struct>(
+    panel: Option<Res<MaterialPanel>>,
+    mut state: ResMut<MaterialParams<M>>,
+    mut shader: ResMut<UserShader<M>>,
+    mut materials: ResMut<Assets<M>>,
+) {
+    let Some(panel) = panel else {
+        return;
+    };
+    if !panel.is_changed() || panel.example != state.example
+    {
+        return;
+    }
+    state.params = panel.params.clone();
+    for name in state.fields {
+        set_field(&mut shader.0, &panel.params, name);
+        for (_handle, material) in materials.iter_mut() {
+            set_field(material, &panel.params, name);
+        }
+    }
+}
+
+/// The panel's controls, shared by every
+/// [`MaterialParamsPlugin`].
+struct MaterialPanelPlugin;
+
+impl Plugin for MaterialPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (drag_sliders, dropdowns, update_panel).chain(),
+        );
+    }
+}
+
+/// A slider for a `Range` parameter, or for one channel of
+/// a `Color` parameter.
+#[derive(Component, Clone, Copy)]
+struct Slider {
+    name: &'static str,
+    channel: Option<usize>,
+}
+
+#[derive(Component)]
+struct SliderFill;
+
+/// Shows the value of a parameter.
+#[derive(Component)]
+struct ValueText(&'static str);
+
+#[derive(Component)]
+struct Swatch(&'static str);
+
+#[derive(Component)]
+struct Dropdown(&'static str);
+
+#[derive(Component)]
+struct DropdownList(&'static str);
+
+#[derive(Component)]
+struct DropdownOption {
+    name: &'static str,
+    value: &'static str,
+}
+
+const PANEL_BACKGROUND: Color =
+    Color::rgba(0.0, 0.0, 0.0, 0.6);
+const TRACK: Color = Color::rgb(0.15, 0.15, 0.15);
+const BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+const CHANNELS: [Color; 3] = [
+    Color::rgb(0.8, 0.2, 0.2),
+    Color::rgb(0.2, 0.8, 0.2),
+    Color::rgb(0.2, 0.4, 0.9),
+];
+
+fn spawn_panel(
+    commands: &mut Commands,
+    params: &Params,
+    fields: &'static [&'static str],
+) {
+    if fields.is_empty() {
+        return;
+    }
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(20.),
+                    top: Val::Px(20.),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(4.),
+                    padding: UiRect::all(Val::Px(8.)),
+                    ..default()
+                },
+                background_color: PANEL_BACKGROUND.into(),
+                ..default()
+            },
+            ExampleEntity,
+        ))
+        .with_children(|panel| {
+            for &name in fields {
+                panel.spawn(TextBundle::from_section(
+                    name,
+                    text_style(),
+                ));
+                match &params.declared(name).kind {
+                    ParamKind::Range { .. }
+                    | ParamKind::Int { .. } => {
+                        panel.spawn(row()).with_children(
+                            |row| {
+                                spawn_slider(
+                                    row,
+                                    Slider {
+                                        name,
+                                        channel: None,
+                                    },
+                                    Color::GRAY,
+                                );
+                                spawn_value(row, name);
+                            },
+                        );
+                    }
+                    ParamKind::Color => {
+                        spawn_color_picker(panel, name)
+                    }
+                    ParamKind::Enum(_)
+                    | ParamKind::Bool => spawn_dropdown(
+                        panel,
+                        name,
+                        options(name, params),
+                    ),
+                    ParamKind::F32
+                    | ParamKind::Vec2
+                    | ParamKind::Path => {
+                        spawn_value(panel, name)
+                    }
+                }
+            }
+        });
+}
+
+fn text_style() -> TextStyle {
+    TextStyle {
+        font_size: 16.0,
+        color: Color::rgb(0.9, 0.9, 0.9),
+        ..default()
+    }
+}
+
+fn row() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(6.),
+            ..default()
+        },
+        ..default()
+    }
+}
+
+fn button() -> ButtonBundle {
+    ButtonBundle {
+        style: Style {
+            padding: UiRect::all(Val::Px(4.)),
+            ..default()
+        },
+        background_color: BUTTON.into(),
+        ..default()
+    }
+}
+
+fn spawn_value(
+    parent: &mut ChildBuilder,
+    name: &'static str,
+) {
+    parent.spawn((
+        TextBundle::from_section("", text_style()),
+        ValueText(name),
+    ));
+}
+
+fn spawn_slider(
+    parent: &mut ChildBuilder,
+    slider: Slider,
+    fill: Color,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(120.),
+                    height: Val::Px(12.),
+                    ..default()
+                },
+                background_color: TRACK.into(),
+                ..default()
+            },
+            RelativeCursorPosition::default(),
+            slider,
+        ))
+        .with_children(|track| {
+            track.spawn((
+                NodeBundle {
+                    style: Style {
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    background_color: fill.into(),
+                    ..default()
+                },
+                SliderFill,
+            ));
+        });
+}
+
+/// A swatch next to a slider for each of red, green and
+/// blue.
+fn spawn_color_picker(
+    parent: &mut ChildBuilder,
+    name: &'static str,
+) {
+    parent.spawn(row()).with_children(|row| {
+        row.spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Px(24.),
+                    height: Val::Px(44.),
+                    ..default()
+                },
+                ..default()
+            },
+            Swatch(name),
+        ));
+        row.spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|sliders| {
+            for (channel, fill) in
+                CHANNELS.into_iter().enumerate()
+            {
+                spawn_slider(
+                    sliders,
+                    Slider {
+                        name,
+                        channel: Some(channel),
+                    },
+                    fill,
+                );
+            }
+        });
+    });
+}
+
+/// A button showing the current value, which opens a list
+/// of the other `values` below it.
+fn spawn_dropdown(
+    parent: &mut ChildBuilder,
+    name: &'static str,
+    values: &'static [&'static str],
+) {
+    parent
+        .spawn((button(), Dropdown(name)))
+        .with_children(|button| spawn_value(button, name));
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: TRACK.into(),
+                ..default()
+            },
+            DropdownList(name),
+        ))
+        .with_children(|list| {
+            for &value in values {
+                list.spawn((
+                    button(),
+                    DropdownOption { name, value },
+                ))
+                .with_children(|option| {
+                    option.spawn(TextBundle::from_section(
+                        value,
+                        text_style(),
+                    ));
+                });
+            }
+        });
+}
+
+/// The values a dropdown offers.
+fn options(
+    name: &str,
+    params: &Params,
+) -> &'static [&'static str] {
+    match &params.declared(name).kind {
+        ParamKind::Enum(values) => values,
+        _ => &["false", "true"],
+    }
+}
+
+fn hex(color: Color) -> String {
+    let [r, g, b, _] = color.as_rgba_u8();
+    format!("{r:02x}{g:02x}{b:02x}")
+}
+
+/// Where the slider's handle is, from 0 to 1.
+fn slider_position(
+    slider: &Slider,
+    params: &Params,
+) -> f32 {
+    match (
+        slider.channel,
+        &params.declared(slider.name).kind,
+    ) {
+        (Some(channel), _) => params
+            .get::<Color>(slider.name)
+            .as_rgba_f32()[channel],
+        (None, ParamKind::Range { min, max }) => {
+            (params.get::<f32>(slider.name) - min)
+                / (max - min)
+        }
+        (None, ParamKind::Int { min, max }) => {
+            (params.get::<u32>(slider.name) - min) as f32
+                / (max - min) as f32
+        }
+        _ => 0.,
+    }
+}
+
+/// The parameter value for a slider at `position`, from 0
+/// to 1.
+fn slider_value(
+    slider: &Slider,
+    params: &Params,
+    position: f32,
+) -> String {
+    match (
+        slider.channel,
+        &params.declared(slider.name).kind,
+    ) {
+        (Some(channel), _) => {
+            let mut rgba = params
+                .get::<Color>(slider.name)
+                .as_rgba_f32();
+            rgba[channel] = position;
+            hex(Color::rgba(
+                rgba[0], rgba[1], rgba[2], rgba[3],
+            ))
+        }
+        (None, ParamKind::Range { min, max }) => {
+            (min + position * (max - min)).to_string()
+        }
+        (None, ParamKind::Int { min, max }) => {
+            let steps = (max - min) as f32;
+            (min + (position * steps).round() as u32)
+                .to_string()
+        }
+        _ => unreachable!(
+            "only numbers and colours have sliders"
+        ),
+    }
+}
+
+fn drag_sliders(
+    sliders: Query<(
+        &Interaction,
+        &RelativeCursorPosition,
+        &Slider,
+    )>,
+    panel: Option<ResMut<MaterialPanel>>,
+) {
+    let Some(mut panel) = panel else {
+        return;
+    };
+    for (interaction, cursor, slider) in sliders.iter() {
+        let (Interaction::Pressed, Some(cursor)) =
+            (interaction, cursor.normalized)
+        else {
+            continue;
+        };
+        let value = slider_value(
+            slider,
+            &panel.params,
+            cursor.x.clamp(0., 1.),
+        );
+        if panel.params.value(slider.name)
+            != Some(value.as_str())
+        {
+            panel.params.insert(
+                slider.name.to_string(),
+                Some(value),
+            );
+        }
+    }
+}
+
+fn dropdowns(
+    buttons: Query<
+        (&Interaction, &Dropdown),
+        Changed<Interaction>,
+    >,
+    options: Query<
+        (&Interaction, &DropdownOption),
+        Changed<Interaction>,
+    >,
+    mut lists: Query<(&mut Style, &DropdownList)>,
+    panel: Option<ResMut<MaterialPanel>>,
+) {
+    let Some(mut panel) = panel else {
+        return;
+    };
+    for (interaction, dropdown) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        for (mut style, list) in lists.iter_mut() {
+            if list.0 == dropdown.0 {
+                style.display = match style.display {
+                    Display::None => Display::Flex,
+                    _ => Display::None,
+                };
+            }
+        }
+    }
+    for (interaction, option) in options.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        panel.params.insert(
+            option.name.to_string(),
+            Some(option.value.to_string()),
+        );
+        for (mut style, list) in lists.iter_mut() {
+            if list.0 == option.name {
+                style.display = Display::None;
+            }
+        }
+    }
+}
+
+fn update_panel(
+    panel: Option<Res<MaterialPanel>>,
+    sliders: Query<(&Slider, &Children)>,
+    mut fills: Query<&mut Style, With<SliderFill>>,
+    mut texts: Query<(&mut Text, &ValueText)>,
+    mut swatches: Query<(&mut BackgroundColor, &Swatch)>,
+) {
+    let Some(panel) = panel else {
+        return;
+    };
+    if !panel.is_changed() {
+        return;
+    }
+    for (slider, children) in sliders.iter() {
+        let position =
+            slider_position(slider, &panel.params);
+        for child in children.iter() {
+            if let Ok(mut style) = fills.get_mut(*child) {
+                style.width = Val::Percent(
+                    position.clamp(0., 1.) * 100.,
+                );
+            }
+        }
+    }
+    for (mut text, value) in texts.iter_mut() {
+        text.sections[0].value =
+            match &panel.params.declared(value.0).kind {
+                ParamKind::Range { .. } => format!(
+                    "{:.3}",
+                    panel.params.get::<f32>(value.0)
+                ),
+                _ => panel
+                    .params
+                    .value(value.0)
+                    .unwrap_or_default()
+                    .to_string(),
+            };
+    }
+    for (mut color, swatch) in swatches.iter_mut() {
+        *color = panel.params.get::<Color>(swatch.0).into();
+    }
+}
@@ -0,0 +1,390 @@
+//! The medial axis, or skeleton, of a shape: the points
+//! inside it with more than one closest point on the
+//! surface, where the gradient of an exact distance
+//! changes direction abruptly. Each point is the centre of
+//! a circle inside the shape touching the surface in two
+//! or more places, with the distance as its radius.
+//!
+//! [`MedialAxis::extract`] works with any [`Sdf`]. In the
+//! examples with a [`CurrentShape`] M draws the skeleton,
+//! and the inscribed circle closest to the mouse.
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    bevy_plugin_shader2d::SdfMouse,
+    center_sdf,
+    gradient::CurrentShape,
+    params::Params,
+    scene_to_world,
+    sdf::{Sdf, Shape},
+    switcher::ActiveExample,
+};
+
+/// How finely the skeleton is looked for.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct MedialAxis {
+    pub enabled: bool,
+    /// How many grid cells fit across the region's shorter
+    /// side.
+    pub cells: u32,
+    /// The smallest angle, in degrees, between the
+    /// gradients at neighbouring grid points that counts as
+    /// the skeleton running between them.
+    pub min_angle: f32,
+}
+
+impl Default for MedialAxis {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cells: 96,
+            min_angle: 30.,
+        }
+    }
+}
+
+/// A point on the skeleton.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MedialPoint {
+    pub position: Vec2,
+    /// The radius of the largest circle around `position`
+    /// inside the shape: the distance to the surface.
+    pub radius: f32,
+}
+
+impl MedialAxis {
+    /// Reads the `medial_axis` parameters the gradient
+    /// examples declare.
+    pub fn from_params(params: &Params) -> Self {
+        Self {
+            enabled: params.get("medial_axis"),
+            cells: params.get("medial_axis_cells"),
+            ..default()
+        }
+    }
+
+    /// The skeleton inside `sdf` between `min` and `max`,
+    /// as polylines that end where branches meet, at the
+    /// tips of branches, or at the edge of the region. For
+    /// the skeleton outside the shape, pass the negated
+    /// distance.
+    pub fn extract(
+        &self,
+        sdf: &(impl Sdf + ?Sized),
+        min: Vec2,
+        max: Vec2,
+    ) -> Vec<Vec<MedialPoint>> {
+        let size = max - min;
+        let spacing =
+            size.min_element() / self.cells.max(1) as f32;
+        let count = (size / spacing).ceil().as_uvec2();
+        let (nx, ny) = (count.x + 1, count.y + 1);
+        // offset differently along x and y, so no grid
+        // point lands on the diagonals or the axes, where
+        // symmetric shapes have their creases
+        let at = |x: u32, y: u32| {
+            min + (Vec2::new(x as f32, y as f32)
+                + Vec2::new(0.5, 0.25))
+                * spacing
+        };
+        let samples: Vec<(f32, Vec2)> = (0..ny)
+            .flat_map(|y| (0..nx).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let point = at(x, y);
+                (
+                    sdf.distance(point),
+                    sdf.gradient(point).normalize_or_zero(),
+                )
+            })
+            .collect();
+        let sample =
+            |x: u32, y: u32| samples[(y * nx + x) as usize];
+        let min_cos = self.min_angle.to_radians().cos();
+
+        let mut graph = Graph::default();
+        // where the skeleton crosses the edge from `(x, y)`
+        // to the next grid point along `step`, keyed by the
+        // edge so both cells sharing it share the point
+        let mut crossings = HashMap::new();
+        let mut crossing =
+            |graph: &mut Graph,
+             x: u32,
+             y: u32,
+             step: UVec2| {
+                *crossings
+                    .entry((x, y, step.x))
+                    .or_insert_with(|| {
+                        let (da, ga) = sample(x, y);
+                        let (db, gb) =
+                            sample(x + step.x, y + step.y);
+                        if da >= 0.
+                            || db >= 0.
+                            || ga.dot(gb) > min_cos
+                        {
+                            return None;
+                        }
+                        let a = at(x, y);
+                        let edge =
+                            at(x + step.x, y + step.y) - a;
+                        let t =
+                            crease(da, ga, db, gb, edge);
+                        let position = a + edge * t;
+                        Some(
+                            graph.add(MedialPoint {
+                                position,
+                                radius: sdf
+                                    .distance(position)
+                                    .abs(),
+                            }),
+                        )
+                    })
+            };
+        for y in 0..ny - 1 {
+            for x in 0..nx - 1 {
+                let ends: Vec<usize> = [
+                    crossing(&mut graph, x, y, UVec2::X),
+                    crossing(
+                        &mut graph,
+                        x + 1,
+                        y,
+                        UVec2::Y,
+                    ),
+                    crossing(
+                        &mut graph,
+                        x,
+                        y + 1,
+                        UVec2::X,
+                    ),
+                    crossing(&mut graph, x, y, UVec2::Y),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                match ends.as_slice() {
+                    [a, b] => graph.connect(*a, *b),
+                    [_, _, ..] => {
+                        // branches meet in this cell
+                        let position =
+                            at(x, y) + spacing / 2.;
+                        let junction =
+                            graph.add(MedialPoint {
+                                position,
+                                radius: sdf
+                                    .distance(position)
+                                    .abs(),
+                            });
+                        for end in ends {
+                            graph.connect(junction, end);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        graph.polylines()
+    }
+
+    /// Draws the skeleton for the whole window, the size of
+    /// `resolution`, in `color`, and the inscribed circle
+    /// whose centre is closest to `mouse`, in scene
+    /// coordinates.
+    pub fn draw(
+        &self,
+        gizmos: &mut Gizmos,
+        polylines: &[Vec<MedialPoint>],
+        resolution: Vec2,
+        mouse: Vec2,
+        color: Color,
+    ) {
+        let to_world = |point: &MedialPoint| {
+            scene_to_world(point.position, resolution)
+        };
+        for polyline in polylines {
+            gizmos.linestrip_2d(
+                polyline.iter().map(to_world),
+                color,
+            );
+        }
+        let closest =
+            polylines.iter().flatten().min_by(|a, b| {
+                a.position
+                    .distance_squared(mouse)
+                    .total_cmp(
+                        &b.position.distance_squared(mouse),
+                    )
+            });
+        if let Some(closest) = closest {
+            gizmos.circle_2d(
+                to_world(closest),
+                closest.radius * resolution.min_element()
+                    / 2.,
+                color,
+            );
+        }
+    }
+}
+
+/// How far along `edge`, from 0 to 1, the planes through
+/// its two ends, with distances `da` and `db` and
+/// gradients `ga` and `gb`, meet.
+fn crease(
+    da: f32,
+    ga: Vec2,
+    db: f32,
+    gb: Vec2,
+    edge: Vec2,
+) -> f32 {
+    let slope = (ga - gb).dot(edge);
+    if slope.abs() < 1e-6 {
+        return 0.5;
+    }
+    ((db - da - gb.dot(edge)) / slope).clamp(0., 1.)
+}
+
+/// Skeleton points and the segments between them.
+#[derive(Default)]
+struct Graph {
+    points: Vec<MedialPoint>,
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    fn add(&mut self, point: MedialPoint) -> usize {
+        self.points.push(point);
+        self.neighbours.push(vec![]);
+        self.points.len() - 1
+    }
+
+    fn connect(&mut self, a: usize, b: usize) {
+        self.neighbours[a].push(b);
+        self.neighbours[b].push(a);
+    }
+
+    /// Walks every segment once: from each end or junction
+    /// until the next one, then around the loops left.
+    fn polylines(&self) -> Vec<Vec<MedialPoint>> {
+        let mut visited = vec![vec![]; self.points.len()];
+        let mut polylines = vec![];
+        let is_node = |index: usize| {
+            self.neighbours[index].len() != 2
+        };
+        let starts = (0..self.points.len())
+            .filter(|index| is_node(*index))
+            .chain(0..self.points.len());
+        for start in starts {
+            for &next in &self.neighbours[start] {
+                if visited[start].contains(&next) {
+                    continue;
+                }
+                let mut polyline = vec![self.points[start]];
+                let (mut previous, mut current) =
+                    (start, next);
+                loop {
+                    visited[previous].push(current);
+                    visited[current].push(previous);
+                    polyline.push(self.points[current]);
+                    if is_node(current) || current == start
+                    {
+                        break;
+                    }
+                    let Some(&following) = self.neighbours
+                        [current]
+                        .iter()
+                        .find(|n| **n != previous)
+                    else {
+                        break;
+                    };
+                    previous = current;
+                    current = following;
+                }
+                polylines.push(polyline);
+            }
+        }
+        polylines
+    }
+}
+
+/// Shows the skeleton of the [`CurrentShape`] while
+/// `example` runs. M turns it on and off.
+pub fn add_medial_axis(
+    app: &mut App,
+    example: &'static str,
+    medial_axis: MedialAxis,
+) {
+    let example = ActiveExample(example);
+    app.add_systems(
+        OnEnter(example),
+        move |mut commands: Commands| {
+            commands.insert_resource(medial_axis)
+        },
+    )
+    .add_systems(
+        OnExit(example),
+        |mut commands: Commands| {
+            commands.remove_resource::<MedialAxis>()
+        },
+    )
+    .add_systems(
+        Update,
+        (toggle_medial_axis, draw_medial_axis)
+            .run_if(in_state(example)),
+    );
+}
+
+fn toggle_medial_axis(
+    keys: Res<Input<KeyCode>>,
+    medial_axis: Option<ResMut<MedialAxis>>,
+) {
+    if let Some(mut medial_axis) = medial_axis {
+        if keys.just_pressed(KeyCode::M) {
+            medial_axis.enabled = !medial_axis.enabled;
+        }
+    }
+}
+
+/// The skeleton last extracted, and what for.
+type Cached =
+    (MedialAxis, Shape, Vec2, Vec<Vec<MedialPoint>>);
+
+fn draw_medial_axis(
+    medial_axis: Option<Res<MedialAxis>>,
+    shape: Option<Res<CurrentShape>>,
+    mouse: Res<SdfMouse>,
+    windows: Query<&Window>,
+    mut cached: Local<Option<Cached>>,
+    mut gizmos: Gizmos,
+) {
+    let (Some(medial_axis), Some(shape), Ok(window)) =
+        (medial_axis, shape, windows.get_single())
+    else {
+        return;
+    };
+    if !medial_axis.enabled {
+        return;
+    }
+    let resolution =
+        Vec2::new(window.width(), window.height());
+    let key = (*medial_axis, shape.0, resolution);
+    let stale = cached.as_ref().map_or(true, |cached| {
+        (cached.0, cached.1, cached.2) != key
+    });
+    if stale {
+        let polylines = medial_axis.extract(
+            &shape.0,
+            center_sdf(Vec2::ZERO, resolution),
+            center_sdf(Vec2::ONE, resolution),
+        );
+        *cached = Some((key.0, key.1, key.2, polylines));
+    }
+    let Some((.., polylines)) = cached.as_ref() else {
+        return;
+    };
+    medial_axis.draw(
+        &mut gizmos,
+        polylines,
+        resolution,
+        center_sdf(mouse.position, resolution),
+        Color::FUCHSIA,
+    );
+}
@@ -0,0 +1,544 @@
+//! Meshes from any [`Sdf`], so a shape built by combining
+//! and smoothing SDFs can be drawn as an ordinary
+//! `Mesh2dHandle`, or exported.
+//!
+//! The surface is traced with [`MarchingSquares`],
+//! simplified, and the inside, holes and all, cut into
+//! triangles by ear clipping.
+
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::Indices, render_resource::PrimitiveTopology,
+    },
+};
+
+use crate::{
+    marching_squares::MarchingSquares,
+    sdf::{sd_box, Sdf},
+};
+
+/// How closely a mesh follows the surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SdfMesher {
+    /// How many grid cells the surface is traced on across
+    /// the region's shorter side.
+    pub cells: u32,
+    /// How far the simplified outline can stray from the
+    /// traced one, in the SDF's units. 0 keeps every
+    /// point.
+    pub tolerance: f32,
+}
+
+impl Default for SdfMesher {
+    fn default() -> Self {
+        Self {
+            cells: 128,
+            tolerance: 0.002,
+        }
+    }
+}
+
+/// Triangles covering the inside of an SDF.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Triangulation {
+    pub positions: Vec<Vec2>,
+    /// Three indices into `positions` per triangle,
+    /// counter-clockwise.
+    pub indices: Vec<u32>,
+    /// How many corners were cut off without their
+    /// triangle when ear clipping found no ear, each
+    /// leaving a sliver uncovered. 0 when the triangles
+    /// cover the whole inside.
+    pub dropped: u32,
+}
+
+impl Triangulation {
+    pub fn area(&self) -> f32 {
+        self.indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|corner| {
+                    self.positions
+                        [triangle[corner] as usize]
+                });
+                (b - a).perp_dot(c - a) / 2.
+            })
+            .sum()
+    }
+
+    /// A mesh of the triangles, in the SDF's coordinates,
+    /// with uvs running from (0, 0) at `min` to (1, 1) at
+    /// `max`. The [`Shape`](crate::sdf::Shape)s have y
+    /// pointing down, so a transform drawing them in world
+    /// space scales y by -1.
+    pub fn to_mesh(&self, min: Vec2, max: Vec2) -> Mesh {
+        let size = (max - min).max(Vec2::splat(1e-6));
+        let mut mesh =
+            Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            self.positions
+                .iter()
+                .map(|position| {
+                    position.extend(0.).to_array()
+                })
+                .collect::<Vec<_>>(),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            vec![[0., 0., 1.]; self.positions.len()],
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_UV_0,
+            self.positions
+                .iter()
+                .map(|position| {
+                    ((*position - min) / size).to_array()
+                })
+                .collect::<Vec<_>>(),
+        );
+        mesh.set_indices(Some(Indices::U32(
+            self.indices.clone(),
+        )));
+        mesh
+    }
+}
+
+impl SdfMesher {
+    /// The surface of `sdf`, cut off at the edges of the
+    /// region between `min` and `max`, as closed,
+    /// simplified loops without the first point repeated.
+    pub fn outlines(
+        &self,
+        sdf: &(impl Sdf + ?Sized),
+        min: Vec2,
+        max: Vec2,
+    ) -> Vec<Vec<Vec2>> {
+        let half = (max - min) / 2.;
+        let center = min + half;
+        // intersected with the region, so shapes running
+        // off its edges still give closed loops
+        let clipped = |point: Vec2| {
+            sdf.distance(point)
+                .max(sd_box(point - center, half))
+        };
+        let margin = half.min_element() * 2.
+            / self.cells.max(1) as f32;
+        MarchingSquares { cells: self.cells }
+            .extract(
+                &clipped,
+                0.,
+                min - margin,
+                max + margin,
+            )
+            .into_iter()
+            .filter(|polyline| {
+                polyline.len() > 3
+                    && polyline.first() == polyline.last()
+            })
+            .map(|mut polyline| {
+                polyline.pop();
+                simplify_loop(&polyline, self.tolerance)
+            })
+            .filter(|outline| {
+                outline.len() >= 3
+                    && signed_area(outline).abs() > 0.
+            })
+            .collect()
+    }
+
+    /// Triangles covering the inside of `sdf` between `min`
+    /// and `max`.
+    pub fn triangulate(
+        &self,
+        sdf: &(impl Sdf + ?Sized),
+        min: Vec2,
+        max: Vec2,
+    ) -> Triangulation {
+        let outlines = self.outlines(sdf, min, max);
+        // how many other outlines each one is inside: even
+        // for the outside of a shape, odd for a hole
+        let depths: Vec<usize> = outlines
+            .iter()
+            .enumerate()
+            .map(|(index, outline)| {
+                outlines
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, polygon)| {
+                        *other != index
+                            && contains(polygon, outline[0])
+                    })
+                    .count()
+            })
+            .collect();
+        let mut triangulation = Triangulation::default();
+        for (index, outline) in outlines.iter().enumerate()
+        {
+            let depth = depths[index];
+            if depth % 2 == 1 {
+                continue;
+            }
+            let holes = outlines
+                .iter()
+                .enumerate()
+                .filter(|(hole, polygon)| {
+                    depths[*hole] == depth + 1
+                        && contains(outline, polygon[0])
+                })
+                .map(|(_, hole)| hole);
+            let mut add = |points: &[Vec2], ccw: bool| {
+                let start =
+                    triangulation.positions.len() as u32;
+                triangulation.positions.extend(points);
+                let indices =
+                    start..start + points.len() as u32;
+                if (signed_area(points) > 0.) == ccw {
+                    indices.collect::<Vec<_>>()
+                } else {
+                    indices.rev().collect()
+                }
+            };
+            let polygon = add(outline, true);
+            let holes: Vec<Vec<u32>> = holes
+                .map(|hole| add(hole, false))
+                .collect();
+            let polygon = bridge_holes(
+                &triangulation.positions,
+                polygon,
+                holes,
+            );
+            triangulation.dropped += ear_clip(
+                &triangulation.positions,
+                polygon,
+                &mut triangulation.indices,
+            );
+        }
+        triangulation
+    }
+
+    /// A mesh of the inside of `sdf` between `min` and
+    /// `max`, as [`Triangulation::to_mesh`] makes it.
+    /// Warns if it doesn't cover all of it.
+    pub fn mesh(
+        &self,
+        sdf: &(impl Sdf + ?Sized),
+        min: Vec2,
+        max: Vec2,
+    ) -> Mesh {
+        let triangulation = self.triangulate(sdf, min, max);
+        if triangulation.dropped > 0 {
+            warn!(
+                "the mesh leaves out the triangles of {} corners",
+                triangulation.dropped
+            );
+        }
+        triangulation.to_mesh(min, max)
+    }
+}
+
+/// Twice the area inside `polygon`, positive when it runs
+/// counter-clockwise.
+fn signed_area(polygon: &[Vec2]) -> f32 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum()
+}
+
+fn contains(polygon: &[Vec2], point: Vec2) -> bool {
+    let mut inside = false;
+    for (a, b) in
+        polygon.iter().zip(polygon.iter().cycle().skip(1))
+    {
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x
+                < a.x
+                    + (point.y - a.y) / (b.y - a.y)
+                        * (b.x - a.x)
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Drops the points of the loop `points` that are within
+/// `tolerance` of the line between the ones kept, by
+/// Douglas-Peucker from the first point and the one
+/// furthest from it.
+fn simplify_loop(
+    points: &[Vec2],
+    tolerance: f32,
+) -> Vec<Vec2> {
+    let furthest = (1..points.len())
+        .max_by(|a, b| {
+            points[*a]
+                .distance_squared(points[0])
+                .total_cmp(
+                    &points[*b].distance_squared(points[0]),
+                )
+        })
+        .unwrap_or(0);
+    // closed, so the second half ends back at the start
+    let ring: Vec<Vec2> = points
+        .iter()
+        .chain(&points[..1])
+        .copied()
+        .collect();
+    let mut kept = vec![false; ring.len()];
+    kept[0] = true;
+    kept[furthest] = true;
+    douglas_peucker(
+        &ring, 0, furthest, tolerance, &mut kept,
+    );
+    douglas_peucker(
+        &ring,
+        furthest,
+        points.len(),
+        tolerance,
+        &mut kept,
+    );
+    points
+        .iter()
+        .zip(kept)
+        .filter(|(_, kept)| *kept)
+        .map(|(point, _)| *point)
+        .collect()
+}
+
+fn douglas_peucker(
+    points: &[Vec2],
+    start: usize,
+    end: usize,
+    tolerance: f32,
+    kept: &mut [bool],
+) {
+    let (a, b) = (points[start], points[end]);
+    let furthest = (start + 1..end)
+        .map(|index| {
+            (
+                index,
+                distance_to_segment(points[index], a, b),
+            )
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+    if let Some((index, distance)) = furthest {
+        if distance > tolerance {
+            kept[index] = true;
+            douglas_peucker(
+                points, start, index, tolerance, kept,
+            );
+            douglas_peucker(
+                points, index, end, tolerance, kept,
+            );
+        }
+    }
+}
+
+fn distance_to_segment(
+    point: Vec2,
+    a: Vec2,
+    b: Vec2,
+) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_squared() > 0. {
+        ((point - a).dot(ab) / ab.length_squared())
+            .clamp(0., 1.)
+    } else {
+        0.
+    };
+    point.distance(a + ab * t)
+}
+
+/// Whether the segments from `a` to `b` and from `c` to
+/// `d` cross, other than at their ends, or `c` or `d` lies
+/// on the first one between its ends.
+fn crosses(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    let side = |from: Vec2, to: Vec2, point: Vec2| {
+        (to - from).perp_dot(point - from)
+    };
+    // a bridge through a vertex, which is on both sides
+    let through = |point: Vec2| {
+        side(a, b, point) == 0.
+            && (point - a).dot(point - b) < 0.
+    };
+    side(a, b, c) * side(a, b, d) < 0.
+        && side(c, d, a) * side(c, d, b) < 0.
+        || through(c)
+        || through(d)
+}
+
+/// Joins each of the clockwise `holes` to the
+/// counter-clockwise `polygon` with a pair of edges there
+/// and back, from its rightmost point to the closest point
+/// it can see, leaving one polygon to clip ears from.
+fn bridge_holes(
+    positions: &[Vec2],
+    mut polygon: Vec<u32>,
+    mut holes: Vec<Vec<u32>>,
+) -> Vec<u32> {
+    let at = |index: &u32| positions[*index as usize];
+    let rightmost = |hole: &[u32]| {
+        (0..hole.len())
+            .max_by(|a, b| {
+                at(&hole[*a]).x.total_cmp(&at(&hole[*b]).x)
+            })
+            .unwrap_or(0)
+    };
+    // rightmost first, so the holes bridged later can't be
+    // cut off by the ones left
+    holes.sort_by(|a, b| {
+        at(&b[rightmost(b)])
+            .x
+            .total_cmp(&at(&a[rightmost(a)]).x)
+    });
+    for (bridged, hole) in holes.iter().enumerate() {
+        let start = rightmost(hole);
+        let from = at(&hole[start]);
+        let edges = |points: &[u32]| {
+            points
+                .iter()
+                .zip(points.iter().cycle().skip(1))
+                .map(|(a, b)| (at(a), at(b)))
+                .collect::<Vec<_>>()
+        };
+        let blocking: Vec<(Vec2, Vec2)> = edges(&polygon)
+            .into_iter()
+            .chain(
+                holes[bridged..]
+                    .iter()
+                    .flat_map(|hole| edges(hole)),
+            )
+            .collect();
+        let by_distance = |a: &usize, b: &usize| {
+            at(&polygon[*a])
+                .distance_squared(from)
+                .total_cmp(
+                    &at(&polygon[*b])
+                        .distance_squared(from),
+                )
+        };
+        let visible = (0..polygon.len())
+            .filter(|index| {
+                let to = at(&polygon[*index]);
+                !blocking
+                    .iter()
+                    .any(|(a, b)| crosses(from, to, *a, *b))
+            })
+            .min_by(by_distance);
+        // rounding can hide every point, and the closest
+        // one is the best guess left
+        let Some(target) = visible.or_else(|| {
+            (0..polygon.len()).min_by(by_distance)
+        }) else {
+            continue;
+        };
+        let around = hole[start..]
+            .iter()
+            .chain(&hole[..=start])
+            .copied();
+        let tail = polygon.split_off(target + 1);
+        polygon.extend(around);
+        polygon.push(polygon[target]);
+        polygon.extend(tail);
+    }
+    polygon
+}
+
+/// Cuts the counter-clockwise `polygon` into triangles,
+/// adding them to `indices`. Every triangle added is
+/// counter-clockwise. Returns how many corners were cut
+/// off without their triangle.
+fn ear_clip(
+    positions: &[Vec2],
+    mut polygon: Vec<u32>,
+    indices: &mut Vec<u32>,
+) -> u32 {
+    let at = |index: u32| positions[index as usize];
+    // corner `k` of `polygon` with its neighbours
+    let triangle = |polygon: &[u32], k: usize| {
+        let count = polygon.len();
+        [
+            polygon[(k + count - 1) % count],
+            polygon[k],
+            polygon[(k + 1) % count],
+        ]
+    };
+    let turn =
+        |[a, b, c]: [Vec2; 3]| (b - a).perp_dot(c - b);
+    // too little turn to cover anything
+    let flat = |[a, b, c]: [Vec2; 3]| {
+        turn([a, b, c]).abs()
+            <= f32::EPSILON
+                * ((b - a).length_squared()
+                    + (c - b).length_squared())
+    };
+    let mut dropped = 0;
+    let mut corner = 0;
+    // how many corners in a row weren't ears
+    let mut misses = 0;
+    while polygon.len() > 3 {
+        let count = polygon.len();
+        corner %= count;
+        let [a, b, c] = triangle(&polygon, corner);
+        let (pa, pb, pc) = (at(a), at(b), at(c));
+        let ear = turn([pa, pb, pc]) > 0.
+            && !polygon.iter().any(|&other| {
+                let point = at(other);
+                ![a, b, c].contains(&other)
+                    && ![pa, pb, pc].contains(&point)
+                    && in_triangle(point, pa, pb, pc)
+            });
+        if flat([pa, pb, pc]) {
+            // nothing to cover
+            polygon.remove(corner);
+            misses = 0;
+        } else if ear {
+            indices.extend([a, b, c]);
+            polygon.remove(corner);
+            misses = 0;
+        } else if misses > count {
+            // with no ears left, from rounding, a convex
+            // corner is cut off without its triangle, which
+            // leaves a sliver uncovered rather than
+            // overlapping
+            let convex = (0..count).find(|&k| {
+                turn(triangle(&polygon, k).map(at)) > 0.
+            });
+            let Some(convex) = convex else {
+                // what's left can't be covered
+                return dropped + count as u32 - 2;
+            };
+            polygon.remove(convex);
+            dropped += 1;
+            misses = 0;
+        } else {
+            corner += 1;
+            misses += 1;
+        }
+    }
+    if polygon.len() == 3 {
+        let [pa, pb, pc] =
+            [polygon[0], polygon[1], polygon[2]].map(at);
+        if turn([pa, pb, pc]) > 0. {
+            indices.extend(polygon);
+        } else if !flat([pa, pb, pc]) {
+            dropped += 1;
+        }
+    }
+    dropped
+}
+
+fn in_triangle(
+    point: Vec2,
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+) -> bool {
+    (b - a).perp_dot(point - a) >= 0.
+        && (c - b).perp_dot(point - b) >= 0.
+        && (a - c).perp_dot(point - c) >= 0.
+}
@@ -0,0 +1,562 @@
+//! Parameters for the examples, read from the page's query
+//! string on the web and from `key=value` arguments natively.
+//!
+//! Parameters are checked against the ones each example
+//! declares in the [registry](crate::registry) before the
+//! app starts, so an example can read them with
+//! [`Params::get`] without handling bad input itself.
+
+use std::fmt;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    bevy_plugin_shader2d::CompareLayout,
+    raymarch::{ColorBy, Projection, Strategy},
+    registry::{Example, Param, ParamKind, VIEW_PARAMS},
+    sdf::Shape,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    /// `None` for a flag given without a value, eg:
+    /// `?example=foo&flag`.
+    values: HashMap<String, Option<String>>,
+    declared: &'static [Param],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamError {
+    /// A `%` that isn't followed by two hex digits, or an
+    /// escape sequence that doesn't decode to UTF-8.
+    BadEncoding(String),
+    NoExample,
+    UnknownExample(String),
+    UnknownParam {
+        example: &'static str,
+        name: String,
+    },
+    MissingValue(String),
+    Invalid {
+        name: String,
+        value: String,
+        expected: String,
+    },
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            ParamError::BadEncoding(text) => {
+                write!(f, "`{text}` is not correctly percent-encoded")
+            }
+            ParamError::NoExample => {
+                write!(f, "no example was picked")
+            }
+            ParamError::UnknownExample(example) => {
+                write!(f, "there is no example called `{example}`")
+            }
+            ParamError::UnknownParam { example, name } => {
+                write!(f, "`{example}` has no parameter called `{name}`")
+            }
+            ParamError::MissingValue(name) => {
+                write!(f, "`{name}` needs a value, eg: `{name}=...`")
+            }
+            ParamError::Invalid {
+                name,
+                value,
+                expected,
+            } => write!(
+                f,
+                "`{name}={value}` is not valid, expected {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+impl Params {
+    /// Parses a query string such as
+    /// `?example=playground&shader=my%20shader.wgsl&flag`.
+    /// `+` decodes to a space, as in form submissions.
+    pub fn parse_query(
+        query: &str,
+    ) -> Result<Self, ParamError> {
+        let mut params = Params::default();
+        for pair in query
+            .trim_start_matches('?')
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+        {
+            let (key, value) = match pair.split_once('=') {
+                Some((key, value)) => {
+                    (key, Some(percent_decode(value)?))
+                }
+                None => (pair, None),
+            };
+            params.insert(percent_decode(key)?, value);
+        }
+        Ok(params)
+    }
+
+    /// Reads `key=value` arguments, with bare arguments
+    /// treated as flags.
+    pub fn from_args(
+        args: impl IntoIterator<Item = String>,
+    ) -> Self {
+        let mut params = Params::default();
+        for arg in args {
+            match arg.split_once('=') {
+                Some((key, value)) => params.insert(
+                    key.to_string(),
+                    Some(value.to_string()),
+                ),
+                None => params.insert(arg, None),
+            }
+        }
+        params
+    }
+
+    pub fn insert(
+        &mut self,
+        name: String,
+        value: Option<String>,
+    ) {
+        self.values.insert(name, value);
+    }
+
+    /// The example these parameters pick.
+    pub fn example(
+        &self,
+    ) -> Result<&'static Example, ParamError> {
+        match self.values.get("example") {
+            Some(Some(id)) => crate::registry::find(id)
+                .ok_or_else(|| {
+                    ParamError::UnknownExample(id.clone())
+                }),
+            _ => Err(ParamError::NoExample),
+        }
+    }
+
+    /// Checks every parameter against the ones `example`
+    /// declares, so that [`Params::get`] can't fail
+    /// afterwards.
+    pub fn validate(
+        mut self,
+        example: &'static Example,
+    ) -> Result<Self, Vec<ParamError>> {
+        let mut errors = vec![];
+        for (name, value) in self.values.iter() {
+            if name == "example" {
+                continue;
+            }
+            let Some(param) = example
+                .params
+                .iter()
+                .chain(VIEW_PARAMS)
+                .find(|param| param.name == name)
+            else {
+                errors.push(ParamError::UnknownParam {
+                    example: example.id,
+                    name: name.clone(),
+                });
+                continue;
+            };
+            if let Err(error) =
+                param.kind.check(name, value.as_deref())
+            {
+                errors.push(error);
+            }
+        }
+        // report errors in a stable order
+        errors.sort_by_key(|error| error.to_string());
+
+        if errors.is_empty() {
+            self.declared = example.params;
+            Ok(self)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The defaults for every parameter `example` declares.
+    pub fn defaults(example: &'static Example) -> Self {
+        Self {
+            values: HashMap::default(),
+            declared: example.params,
+        }
+    }
+
+    /// The value of a declared parameter, or its default if
+    /// it wasn't given.
+    ///
+    /// Panics if `name` isn't one of the example's declared
+    /// parameters, or if the parameters haven't been
+    /// [validated](Params::validate).
+    pub fn get<T: FromParam>(&self, name: &str) -> T {
+        T::from_param(self.value(name)).unwrap_or_else(
+            |expected| {
+                panic!("`{name}` was not validated, expected {expected}")
+            },
+        )
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.values.remove(name);
+    }
+
+    /// Whether the parameter was given, rather than left
+    /// to its default.
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    /// A query string that picks `example` with every
+    /// parameter that isn't at its default, in the order
+    /// they're declared.
+    pub fn to_query(&self, example: &str) -> String {
+        let mut query =
+            format!("?example={}", percent_encode(example));
+        for param in self.declared.iter().chain(VIEW_PARAMS)
+        {
+            let Some(value) = self.values.get(param.name)
+            else {
+                continue;
+            };
+            if value.as_deref() == Some(param.default) {
+                continue;
+            }
+            query.push('&');
+            query.push_str(&percent_encode(param.name));
+            if let Some(value) = value {
+                query.push('=');
+                query.push_str(&percent_encode(value));
+            }
+        }
+        query
+    }
+
+    /// The unparsed value of a declared parameter, or its
+    /// default. `None` for a flag given without a value.
+    ///
+    /// Panics if `name` isn't one of the example's declared
+    /// parameters.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        match self.values.get(name) {
+            Some(value) => value.as_deref(),
+            None => Some(self.declared(name).default),
+        }
+    }
+
+    /// The declaration of one of the example's parameters,
+    /// or of the [`VIEW_PARAMS`].
+    ///
+    /// Panics if there is no parameter called `name`.
+    pub fn declared(&self, name: &str) -> &'static Param {
+        self.declared
+            .iter()
+            .chain(VIEW_PARAMS)
+            .find(|param| param.name == name)
+            .unwrap_or_else(|| {
+                panic!("`{name}` is not a declared parameter")
+            })
+    }
+}
+
+/// A type a parameter value can be parsed into. `value` is
+/// `None` for a flag given without a value.
+pub trait FromParam: Sized {
+    /// On failure, returns a description of the values that
+    /// would have been accepted.
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String>;
+}
+
+impl FromParam for String {
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String> {
+        value
+            .map(str::to_string)
+            .ok_or("a value".to_string())
+    }
+}
+
+impl FromParam for bool {
+    /// A flag on its own means `true`.
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String> {
+        match value {
+            None | Some("true" | "on" | "yes" | "1") => {
+                Ok(true)
+            }
+            Some("false" | "off" | "no" | "0") => Ok(false),
+            Some(_) => Err("true or false".to_string()),
+        }
+    }
+}
+
+impl FromParam for f32 {
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String> {
+        value
+            .and_then(|value| value.trim().parse().ok())
+            .filter(|value: &f32| value.is_finite())
+            .ok_or("a number".to_string())
+    }
+}
+
+impl FromParam for u32 {
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String> {
+        value
+            .and_then(|value| value.trim().parse().ok())
+            .ok_or("a whole number".to_string())
+    }
+}
+
+impl FromParam for Vec2 {
+    /// Two numbers separated by a comma, eg: `0.5,0.25`.
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String> {
+        let expected =
+            || "two numbers, eg: 0.5,0.25".to_string();
+        let (x, y) = value
+            .and_then(|value| value.split_once(','))
+            .ok_or_else(expected)?;
+        Ok(Vec2::new(
+            f32::from_param(Some(x))
+                .map_err(|_| expected())?,
+            f32::from_param(Some(y))
+                .map_err(|_| expected())?,
+        ))
+    }
+}
+
+impl FromParam for Color {
+    /// A hex colour, with or without the `#` (which has to
+    /// be written `%23` in a url), eg: `ff8800` or `#f80`.
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String> {
+        value
+            .and_then(|value| Color::hex(value).ok())
+            .ok_or("a hex colour, eg: ff8800".to_string())
+    }
+}
+
+impl FromParam for Shape {
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String> {
+        value.and_then(Shape::from_name).ok_or(format!(
+            "one of {}",
+            Shape::NAMES.join(", ")
+        ))
+    }
+}
+
+impl FromParam for CompareLayout {
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String> {
+        value.and_then(CompareLayout::from_name).ok_or(
+            format!(
+                "one of {}",
+                CompareLayout::NAMES.join(", ")
+            ),
+        )
+    }
+}
+
+impl FromParam for Strategy {
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String> {
+        value.and_then(Strategy::from_name).ok_or(format!(
+            "one of {}",
+            Strategy::NAMES.join(", ")
+        ))
+    }
+}
+
+impl FromParam for Projection {
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String> {
+        value.and_then(Projection::from_name).ok_or(
+            format!(
+                "one of {}",
+                Projection::NAMES.join(", ")
+            ),
+        )
+    }
+}
+
+impl FromParam for ColorBy {
+    fn from_param(
+        value: Option<&str>,
+    ) -> Result<Self, String> {
+        value.and_then(ColorBy::from_name).ok_or(format!(
+            "one of {}",
+            ColorBy::NAMES.join(", ")
+        ))
+    }
+}
+
+impl ParamKind {
+    fn check(
+        &self,
+        name: &str,
+        value: Option<&str>,
+    ) -> Result<(), ParamError> {
+        let expected = match self {
+            ParamKind::Bool => {
+                bool::from_param(value).err()
+            }
+            ParamKind::F32 => f32::from_param(value).err(),
+            ParamKind::Range { min, max } => {
+                match f32::from_param(value) {
+                    Ok(number)
+                        if (*min..=*max).contains(&number) =>
+                    {
+                        None
+                    }
+                    _ => Some(format!(
+                        "a number from {min} to {max}"
+                    )),
+                }
+            }
+            ParamKind::Int { min, max } => {
+                match u32::from_param(value) {
+                    Ok(number)
+                        if (*min..=*max).contains(&number) =>
+                    {
+                        None
+                    }
+                    _ => Some(format!(
+                        "a whole number from {min} to {max}"
+                    )),
+                }
+            }
+            ParamKind::Vec2 => {
+                Vec2::from_param(value).err()
+            }
+            ParamKind::Color => {
+                Color::from_param(value).err()
+            }
+            ParamKind::Enum(values) => match value {
+                Some(value) if values.contains(&value) => {
+                    None
+                }
+                _ => Some(format!(
+                    "one of {}",
+                    values.join(", ")
+                )),
+            },
+            ParamKind::Path => {
+                String::from_param(value).err()
+            }
+        };
+        match (expected, value) {
+            (None, _) => Ok(()),
+            (Some(_), None) => Err(
+                ParamError::MissingValue(name.to_string()),
+            ),
+            (Some(expected), Some(value)) => {
+                Err(ParamError::Invalid {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                    expected,
+                })
+            }
+        }
+    }
+}
+
+fn percent_decode(
+    text: &str,
+) -> Result<String, ParamError> {
+    let bad_encoding =
+        || ParamError::BadEncoding(text.to_string());
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut input = text.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'%' => {
+                let hex = [
+                    input
+                        .next()
+                        .ok_or_else(bad_encoding)?,
+                    input
+                        .next()
+                        .ok_or_else(bad_encoding)?,
+                ];
+                // `from_str_radix` takes a sign too
+                if !hex.iter().all(u8::is_ascii_hexdigit) {
+                    return Err(bad_encoding());
+                }
+                let hex = std::str::from_utf8(&hex)
+                    .map_err(|_| bad_encoding())?;
+                bytes.push(
+                    u8::from_str_radix(hex, 16)
+                        .map_err(|_| bad_encoding())?,
+                );
+            }
+            b'+' => bytes.push(b' '),
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| bad_encoding())
+}
+
+/// Escapes everything but letters, digits and `-._~,` so
+/// that [`percent_decode`] gives `text` back.
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b',' => (byte as char).to_string(),
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// The page the web build shows instead of an example when
+/// its parameters are wrong.
+pub fn errors_html(errors: &[ParamError]) -> String {
+    let items = errors
+        .iter()
+        .map(|error| {
+            format!(
+                "<li>{}</li>",
+                escape_html(&error.to_string())
+            )
+        })
+        .collect::<String>();
+    format!(
+        r#"<main class="errors"><h1>Can't start the example</h1><ul>{items}</ul><p><a href="?">See all examples</a></p></main>"#
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,299 @@
+//! Marching rays through any [`Sdf`], counting the steps,
+//! to see where a marching strategy gets expensive: near
+//! edges, and where rays graze a surface.
+//!
+//! [`StepHeatmap`] colours a picture by the steps the ray
+//! for each pixel took, or by why it stopped. The
+//! `raymarch-cost` example does the same on the GPU with
+//! `march` in `raymarch-cost.wgsl`, and the [CPU
+//! renderer](crate::cpu_renderer) with
+//! [`ColorMode::Steps`](crate::cpu_renderer::ColorMode::Steps).
+
+use bevy::prelude::*;
+
+use crate::{color_map::ColorMap, sdf::Sdf};
+
+/// How far a ray moves at each step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// Plain sphere tracing: as far as the distance.
+    #[default]
+    Sphere,
+    /// Over-relaxed sphere tracing (Keinert et al., 2014):
+    /// [`RayMarcher::relaxation`] times the distance,
+    /// stepping back to plain sphere tracing when that
+    /// overshoots.
+    Relaxed,
+}
+
+impl Strategy {
+    /// The names the `strategy` parameter takes, in the
+    /// order of the ids the shader uses.
+    pub const NAMES: [&'static str; 2] =
+        ["sphere", "relaxed"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sphere" => Some(Strategy::Sphere),
+            "relaxed" => Some(Strategy::Relaxed),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        *self as u32
+    }
+}
+
+/// Why a ray stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    /// Closer to a surface than [`RayMarcher::epsilon`].
+    Hit,
+    /// Further than [`RayMarcher::max_distance`] from its
+    /// origin.
+    Escaped,
+    /// Took [`RayMarcher::max_steps`] steps without doing
+    /// either.
+    OutOfSteps,
+}
+
+impl Termination {
+    /// Green for hits, blue for escaped rays and red for
+    /// rays that ran out of steps, in linear RGB.
+    pub fn color(&self) -> Vec3 {
+        match self {
+            Termination::Hit => Vec3::new(0.1, 0.8, 0.2),
+            Termination::Escaped => {
+                Vec3::new(0.1, 0.3, 0.9)
+            }
+            Termination::OutOfSteps => {
+                Vec3::new(0.9, 0.1, 0.1)
+            }
+        }
+    }
+}
+
+/// Where a ray stopped, and after how many steps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct March {
+    pub steps: u32,
+    /// How far along the ray it stopped.
+    pub distance: f32,
+    pub termination: Termination,
+}
+
+/// The settings a ray is marched with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayMarcher {
+    pub strategy: Strategy,
+    /// How much further than the distance
+    /// [`Strategy::Relaxed`] steps, from 1 (plain sphere
+    /// tracing) to 2.
+    pub relaxation: f32,
+    pub max_steps: u32,
+    pub max_distance: f32,
+    pub epsilon: f32,
+}
+
+impl Default for RayMarcher {
+    /// The settings `raymarch-cost.wgsl` uses, apart from
+    /// the strategy, relaxation and steps its material
+    /// sets.
+    fn default() -> Self {
+        Self {
+            strategy: Strategy::Sphere,
+            relaxation: 1.6,
+            max_steps: 64,
+            max_distance: 4.,
+            epsilon: 0.001,
+        }
+    }
+}
+
+impl RayMarcher {
+    /// Marches from `origin` along `direction`, which
+    /// should be normalized.
+    pub fn march(
+        &self,
+        sdf: &impl Sdf,
+        origin: Vec2,
+        direction: Vec2,
+    ) -> March {
+        let mut omega = match self.strategy {
+            Strategy::Sphere => 1.,
+            Strategy::Relaxed => self.relaxation,
+        };
+        let mut t = 0.;
+        let mut previous_radius = 0.;
+        let mut step: f32 = 0.;
+        for i in 0..self.max_steps {
+            let signed_radius =
+                sdf.distance(origin + direction * t);
+            let radius = signed_radius.abs();
+            // the circles of the last two points don't
+            // overlap, so the relaxed step may have jumped
+            // over a surface: go back to where plain sphere
+            // tracing would have stepped
+            let overshot = omega > 1.
+                && radius + previous_radius < step;
+            if overshot {
+                step -= omega * step;
+                omega = 1.;
+            } else {
+                step = signed_radius * omega;
+            }
+            previous_radius = radius;
+            let termination = if overshot {
+                None
+            } else if signed_radius < self.epsilon {
+                Some(Termination::Hit)
+            } else if t > self.max_distance {
+                Some(Termination::Escaped)
+            } else {
+                None
+            };
+            if let Some(termination) = termination {
+                return March {
+                    steps: i + 1,
+                    distance: t,
+                    termination,
+                };
+            }
+            t += step;
+        }
+        March {
+            steps: self.max_steps,
+            distance: t,
+            termination: Termination::OutOfSteps,
+        }
+    }
+}
+
+/// Which ray a pixel shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Projection {
+    /// The ray from [`StepHeatmap::origin`] through the
+    /// pixel, so each direction is a line out from the
+    /// origin.
+    #[default]
+    Radial,
+    /// The ray starting at the pixel, in the direction of
+    /// [`StepHeatmap::angle`].
+    Orthographic,
+}
+
+impl Projection {
+    /// The names the `projection` parameter takes, in the
+    /// order of the ids the shader uses.
+    pub const NAMES: [&'static str; 2] =
+        ["radial", "orthographic"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "radial" => Some(Projection::Radial),
+            "orthographic" => {
+                Some(Projection::Orthographic)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        *self as u32
+    }
+}
+
+/// What a pixel's colour shows about its ray.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorBy {
+    /// The steps taken, through the viridis colour map from
+    /// none to [`RayMarcher::max_steps`].
+    #[default]
+    Steps,
+    /// The [`Termination`] colour, darker for fewer steps.
+    Termination,
+}
+
+impl ColorBy {
+    /// The names the `color_by` parameter takes, in the order
+    /// of the ids the shader uses.
+    pub const NAMES: [&'static str; 2] =
+        ["steps", "termination"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "steps" => Some(ColorBy::Steps),
+            "termination" => Some(ColorBy::Termination),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        *self as u32
+    }
+}
+
+/// A picture of how much marching each pixel's ray costs.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct StepHeatmap {
+    pub marcher: RayMarcher,
+    pub projection: Projection,
+    /// Where [`Projection::Radial`] rays start, in scene
+    /// coordinates.
+    pub origin: Vec2,
+    /// The direction of [`Projection::Orthographic`] rays,
+    /// in degrees counter-clockwise from the right.
+    pub angle: f32,
+    pub color_by: ColorBy,
+}
+
+impl StepHeatmap {
+    /// The ray for the pixel at `coord`, in scene
+    /// coordinates.
+    pub fn ray(&self, coord: Vec2) -> (Vec2, Vec2) {
+        match self.projection {
+            Projection::Radial => (
+                self.origin,
+                (coord - self.origin).normalize_or_zero(),
+            ),
+            Projection::Orthographic => {
+                // the scene's y points down
+                let angle = self.angle.to_radians();
+                (
+                    coord,
+                    Vec2::new(angle.cos(), -angle.sin()),
+                )
+            }
+        }
+    }
+
+    /// The linear colour for the pixel at `coord`, with the
+    /// surface outlined in white. `width` is the distance
+    /// one pixel covers.
+    pub fn color(
+        &self,
+        sdf: &impl Sdf,
+        coord: Vec2,
+        width: f32,
+    ) -> Vec4 {
+        let (origin, direction) = self.ray(coord);
+        let march =
+            self.marcher.march(sdf, origin, direction);
+        let cost = march.steps as f32
+            / self.marcher.max_steps.max(1) as f32;
+        let rgb = match self.color_by {
+            ColorBy::Steps => ColorMap::Viridis
+                .ramp(cost)
+                .expect("viridis has stops"),
+            ColorBy::Termination => {
+                march.termination.color()
+                    * (0.35 + 0.65 * cost)
+            }
+        };
+        let outline = (1.5
+            - sdf.distance(coord).abs() / width.max(1e-6))
+        .clamp(0., 1.);
+        rgb.lerp(Vec3::ONE, outline).extend(1.)
+    }
+}
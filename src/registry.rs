@@ -0,0 +1,620 @@
+//! Every example app, with the metadata the launcher, the
+//! web index page and the README list are generated from.
+
+use bevy::prelude::*;
+
+use crate::{
+    apps::{
+        compare_circle_and_border::{
+            self, CompareCircleAndBorderConfig,
+            CompareCircleAndBorderPlugin,
+        },
+        compare_shapes::{
+            self, CompareShapesConfig, CompareShapesPlugin,
+        },
+        playground::{
+            self, PlaygroundConfig, PlaygroundPlugin,
+        },
+        raymarch_cost::{
+            self, RaymarchCostConfig, RaymarchCostPlugin,
+            SdfDemoMaterial as RaymarchCostMaterial,
+        },
+        single_sdf_distance_as_border::{
+            self, SdfBorderPlugin,
+            SdfDemoMaterial as BorderMaterial,
+        },
+        single_sdf_distance_as_circle::{
+            self, Lights, SdfCirclePlugin,
+            SdfDemoMaterial as CircleMaterial,
+        },
+        single_sdf_distance_as_gradient::{
+            self, SdfDemoMaterial as GradientMaterial,
+            SdfGradientPlugin,
+        },
+        single_sdf_distance_as_gradient_with_abs_mouse::{
+            self, SdfDemoMaterial as AbsMouseMaterial,
+            SdfGradientWithAbsMousePlugin,
+        },
+        single_sdf_distance_as_gradient_with_algorithm::{
+            self, SdfDemoMaterial as AlgorithmMaterial,
+            SdfGradientWithAlgorithmPlugin,
+        },
+        single_sdf_distance_as_gradient_with_mouse::{
+            self, SdfDemoMaterial as WithMouseMaterial,
+            SdfGradientWithMousePlugin,
+        },
+        single_sdf_distance_as_gradient_with_mouse_and_inner_ray::{
+            self, SdfDemoMaterial as InnerRayMaterial,
+            SdfGradientWithInnerRayPlugin,
+        },
+    },
+    bevy_plugin_shader2d::{CompareLayout, MAX_ZOOM},
+    gradient::{self, GradientConfig},
+    material_params::MaterialParamsPlugin,
+    params::Params,
+    raymarch::{ColorBy, Projection, Strategy},
+    sdf::Shape,
+};
+
+/// Where the web build is deployed.
+pub const LIVE_DEMO_URL: &str =
+    "https://raymarch-2d-examples.netlify.app/";
+
+pub struct Example {
+    /// The name used in `?example=` and on the command line.
+    pub id: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    pub params: &'static [Param],
+    /// The parameters that set material fields, which the
+    /// [material panel](crate::material_params) and a
+    /// [storyboard](crate::storyboard) can change while the
+    /// example runs.
+    pub fields: &'static [&'static str],
+    /// Adds the example's plugin, configured from its
+    /// parameters, to an app with the
+    /// [`SdfCorePlugin`](crate::bevy_plugin_shader2d::SdfCorePlugin).
+    pub add: fn(&mut App, &Params),
+}
+
+#[derive(Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub kind: ParamKind,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+#[derive(Debug)]
+pub enum ParamKind {
+    Bool,
+    F32,
+    /// A number from `min` to `max`, shown as a slider in
+    /// the [material panel](crate::material_params).
+    Range {
+        min: f32,
+        max: f32,
+    },
+    /// A whole number from `min` to `max`, shown as a
+    /// slider like a range.
+    Int {
+        min: u32,
+        max: u32,
+    },
+    /// Two numbers separated by a comma.
+    Vec2,
+    /// A hex colour.
+    Color,
+    /// One of a fixed set of values.
+    Enum(&'static [&'static str]),
+    /// An asset path, relative to `assets/` unless it's
+    /// absolute.
+    Path,
+}
+
+impl ParamKind {
+    /// A short description of the accepted values, for help
+    /// text.
+    pub fn describe(&self) -> String {
+        match self {
+            ParamKind::Bool => "true|false".to_string(),
+            ParamKind::F32 => "number".to_string(),
+            ParamKind::Range { min, max } => {
+                format!("{min}..{max}")
+            }
+            ParamKind::Int { min, max } => {
+                format!("{min}..{max}")
+            }
+            ParamKind::Vec2 => "x,y".to_string(),
+            ParamKind::Color => "rrggbb".to_string(),
+            ParamKind::Enum(values) => values.join("|"),
+            ParamKind::Path => "path".to_string(),
+        }
+    }
+}
+
+/// The values for `shape=` in the plain gradient example,
+/// where `mouse` picks the shape from the mouse position.
+const SHAPE_OR_MOUSE: [&str; 6] =
+    ["mouse", "circle", "box", "triangle", "x", "cool-s"];
+
+/// The gradient parameters that set material fields, so
+/// they can be changed in the panel. `shape` is left to
+/// [`CurrentShape`](crate::gradient::CurrentShape).
+const GRADIENT_FIELDS: [&str; 6] = [
+    "inside",
+    "outside",
+    "background",
+    "color_map",
+    "distance_range",
+    "log_scale",
+];
+
+/// The raymarching cost parameters, which all set
+/// material fields.
+const RAYMARCH_COST_FIELDS: [&str; 6] = [
+    "strategy",
+    "relaxation",
+    "projection",
+    "angle",
+    "color_by",
+    "max_steps",
+];
+
+/// How a comparison shows its two sides.
+const LAYOUT_PARAM: Param = Param {
+    name: "layout",
+    kind: ParamKind::Enum(&CompareLayout::NAMES),
+    default: "side-by-side",
+    description: "Each side in its own half of the window, or both covering it with a divider to drag.",
+};
+
+/// The parameters every example takes, for the view a
+/// [link](crate::link) reproduces.
+pub const VIEW_PARAMS: &[Param] = &[
+    Param {
+        name: "mouse",
+        kind: ParamKind::Vec2,
+        default: "0.5,0.5",
+        description: "Pins the mouse at this position, from 0,0 in the top left corner to 1,1 in the bottom right. P pins and unpins it.",
+    },
+    Param {
+        name: "zoom",
+        kind: ParamKind::Range {
+            min: 1.,
+            max: MAX_ZOOM,
+        },
+        default: "1",
+        description: "How far the camera is zoomed in. The mouse wheel zooms.",
+    },
+    Param {
+        name: "center",
+        kind: ParamKind::Vec2,
+        default: "0,0",
+        description: "Where the camera looks, from -1 to 1 across the window.",
+    },
+    Param {
+        name: "contours",
+        kind: ParamKind::Bool,
+        default: "false",
+        description: "Draws isolines over the example. C turns them on and off.",
+    },
+    Param {
+        name: "contour_spacing",
+        kind: ParamKind::Range {
+            min: 0.01,
+            max: 1.,
+        },
+        default: "0.1",
+        description: "The distance between two isolines.",
+    },
+    Param {
+        name: "contour_thickness",
+        kind: ParamKind::Range {
+            min: 0.5,
+            max: 10.,
+        },
+        default: "1",
+        description: "The width of an isoline, in pixels.",
+    },
+    Param {
+        name: "contour_major",
+        kind: ParamKind::Int {
+            min: 0,
+            max: 20,
+        },
+        default: "5",
+        description: "Every how many isolines one is drawn stronger, 0 for none.",
+    },
+    Param {
+        name: "contour_fade",
+        kind: ParamKind::Range {
+            min: 0.,
+            max: 20.,
+        },
+        default: "4",
+        description: "How quickly the colour fades towards the surface while the isolines are on, 0 for not at all.",
+    },
+];
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        id: single_sdf_distance_as_gradient::ID,
+        title: "Distance as a gradient",
+        description: "Colours every pixel by its distance to a shape: orange outside, blue inside. Moving the mouse across the window picks the shape.",
+        params: &gradient::params(&SHAPE_OR_MOUSE, "mouse"),
+        fields: &GRADIENT_FIELDS,
+        add: |app, params| {
+            app.add_plugins((
+                SdfGradientPlugin {
+                    config: GradientConfig::from_params(
+                        params,
+                    ),
+                },
+                MaterialParamsPlugin::<GradientMaterial>::new(
+                    single_sdf_distance_as_gradient::ID,
+                    params,
+                    &GRADIENT_FIELDS,
+                ),
+            ));
+        },
+    },
+    Example {
+        id: single_sdf_distance_as_gradient_with_mouse::ID,
+        title: "Distance to the center from the mouse",
+        description: "The distance gradient with a line from the origin to the mouse and a button to cycle through shapes.",
+        params: &gradient::params(&Shape::NAMES, "circle"),
+        fields: &GRADIENT_FIELDS,
+        add: |app, params| {
+            app.add_plugins((
+                SdfGradientWithMousePlugin {
+                    config: GradientConfig::from_params(
+                        params,
+                    ),
+                },
+                MaterialParamsPlugin::<WithMouseMaterial>::new(
+                    single_sdf_distance_as_gradient_with_mouse::ID,
+                    params,
+                    &GRADIENT_FIELDS,
+                ),
+            ));
+        },
+    },
+    Example {
+        id: single_sdf_distance_as_gradient_with_mouse_and_inner_ray::ID,
+        title: "Distance from the mouse to the shape",
+        description: "Draws the ray from the mouse to the closest point on the shape, with its length.",
+        params: &gradient::params(&Shape::NAMES, "circle"),
+        fields: &GRADIENT_FIELDS,
+        add: |app, params| {
+            app.add_plugins((
+                SdfGradientWithInnerRayPlugin {
+                    config: GradientConfig::from_params(
+                        params,
+                    ),
+                },
+                MaterialParamsPlugin::<InnerRayMaterial>::new(
+                    single_sdf_distance_as_gradient_with_mouse_and_inner_ray::ID,
+                    params,
+                    &GRADIENT_FIELDS,
+                ),
+            ));
+        },
+    },
+    Example {
+        id: single_sdf_distance_as_gradient_with_abs_mouse::ID,
+        title: "Folding the mouse into the first quadrant",
+        description: "Shows how `abs` mirrors a point into the first quadrant, which is how the box SDF only needs to handle one corner.",
+        params: &gradient::params(&Shape::NAMES, "box"),
+        fields: &GRADIENT_FIELDS,
+        add: |app, params| {
+            app.add_plugins((
+                SdfGradientWithAbsMousePlugin {
+                    config: GradientConfig::from_params(
+                        params,
+                    ),
+                },
+                MaterialParamsPlugin::<AbsMouseMaterial>::new(
+                    single_sdf_distance_as_gradient_with_abs_mouse::ID,
+                    params,
+                    &GRADIENT_FIELDS,
+                ),
+            ));
+        },
+    },
+    Example {
+        id: single_sdf_distance_as_gradient_with_algorithm::ID,
+        title: "The box SDF, step by step",
+        description: "Every intermediate value of the box SDF for the mouse position, with matching gizmos.",
+        params: &gradient::params(&Shape::NAMES, "box"),
+        fields: &GRADIENT_FIELDS,
+        add: |app, params| {
+            app.add_plugins((
+                SdfGradientWithAlgorithmPlugin {
+                    config: GradientConfig::from_params(
+                        params,
+                    ),
+                },
+                MaterialParamsPlugin::<AlgorithmMaterial>::new(
+                    single_sdf_distance_as_gradient_with_algorithm::ID,
+                    params,
+                    &GRADIENT_FIELDS,
+                ),
+            ));
+        },
+    },
+    Example {
+        id: single_sdf_distance_as_circle::ID,
+        title: "Distance as a circle",
+        description: "A circle around the mouse whose radius is the distance to the scene, so it always just touches the closest surface.",
+        params: &[Param {
+            name: "lights",
+            // `lights` is the index: 0 off, 1 on
+            kind: ParamKind::Enum(&["off", "on"]),
+            default: "on",
+            description: "Fill the shape with white (on) or leave it black (off).",
+        }],
+        fields: &["lights"],
+        add: |app, params| {
+            app.add_plugins((
+                SdfCirclePlugin::default(),
+                MaterialParamsPlugin::<CircleMaterial>::new(
+                    single_sdf_distance_as_circle::ID,
+                    params,
+                    &["lights"],
+                ),
+            ));
+        },
+    },
+    Example {
+        id: single_sdf_distance_as_border::ID,
+        title: "Distance as a border",
+        description: "Highlights every point that is as far from the scene as the mouse is.",
+        params: &[Param {
+            name: "thickness",
+            kind: ParamKind::Range {
+                min: 0.001,
+                max: 0.05,
+            },
+            default: "0.005",
+            description: "How thick the border is.",
+        }],
+        fields: &["thickness"],
+        add: |app, params| {
+            app.add_plugins((
+                SdfBorderPlugin,
+                MaterialParamsPlugin::<BorderMaterial>::new(
+                    single_sdf_distance_as_border::ID,
+                    params,
+                    &["thickness"],
+                ),
+            ));
+        },
+    },
+    Example {
+        id: compare_shapes::ID,
+        title: "Two shapes side by side",
+        description: "The distance gradients of two shapes next to each other, zooming together.",
+        params: &[
+            Param {
+                name: "left",
+                kind: ParamKind::Enum(&Shape::NAMES),
+                default: "circle",
+                description: "The shape on the left.",
+            },
+            Param {
+                name: "right",
+                kind: ParamKind::Enum(&Shape::NAMES),
+                default: "cool-s",
+                description: "The shape on the right.",
+            },
+            LAYOUT_PARAM,
+        ],
+        fields: &[],
+        add: |app, params| {
+            app.add_plugins(CompareShapesPlugin {
+                config: CompareShapesConfig {
+                    left: params.get("left"),
+                    right: params.get("right"),
+                    layout: params.get("layout"),
+                    ..default()
+                },
+            });
+        },
+    },
+    Example {
+        id: compare_circle_and_border::ID,
+        title: "Distance as a circle and as a border",
+        description: "The circle and the border examples next to each other, following the same mouse.",
+        params: &[
+            Param {
+                name: "lights",
+                kind: ParamKind::Enum(&["off", "on"]),
+                default: "on",
+                description: "Fill the shape on the left with white (on) or leave it black (off).",
+            },
+            Param {
+                name: "thickness",
+                kind: ParamKind::Range {
+                    min: 0.001,
+                    max: 0.05,
+                },
+                default: "0.005",
+                description: "How thick the border on the right is.",
+            },
+            LAYOUT_PARAM,
+        ],
+        fields: &[],
+        add: |app, params| {
+            app.add_plugins(CompareCircleAndBorderPlugin {
+                config: CompareCircleAndBorderConfig {
+                    lights: match params.value("lights") {
+                        Some("off") => Lights::Off,
+                        _ => Lights::On,
+                    },
+                    thickness: params.get("thickness"),
+                    layout: params.get("layout"),
+                },
+            });
+        },
+    },
+    Example {
+        id: raymarch_cost::ID,
+        title: "Raymarching cost",
+        description: "Colours every pixel by the steps its ray takes to reach the shape, or by why it stops, to compare marching strategies. Rays start at the mouse, or at every pixel in one direction.",
+        params: &[
+            Param {
+                name: "shape",
+                kind: ParamKind::Enum(&Shape::NAMES),
+                default: "box",
+                description: "The shape to march towards.",
+            },
+            Param {
+                name: "strategy",
+                kind: ParamKind::Enum(&Strategy::NAMES),
+                default: "sphere",
+                description: "Plain sphere tracing, or over-relaxed steps that fall back to it when they overshoot.",
+            },
+            Param {
+                name: "relaxation",
+                kind: ParamKind::Range {
+                    min: 1.,
+                    max: 2.,
+                },
+                default: "1.6",
+                description: "How many times the distance a relaxed step goes.",
+            },
+            Param {
+                name: "projection",
+                kind: ParamKind::Enum(&Projection::NAMES),
+                default: "radial",
+                description: "Rays from the mouse through every pixel (radial), or from every pixel towards `angle` (orthographic).",
+            },
+            Param {
+                name: "angle",
+                kind: ParamKind::Range {
+                    min: 0.,
+                    max: 360.,
+                },
+                default: "0",
+                description: "The direction of orthographic rays, in degrees counter-clockwise from the right.",
+            },
+            Param {
+                name: "color_by",
+                kind: ParamKind::Enum(&ColorBy::NAMES),
+                default: "steps",
+                description: "Colour by the steps taken, or by whether the ray hit (green), escaped (blue) or ran out of steps (red).",
+            },
+            Param {
+                name: "max_steps",
+                kind: ParamKind::Int {
+                    min: 1,
+                    max: 256,
+                },
+                default: "64",
+                description: "The steps a ray gets before giving up.",
+            },
+        ],
+        fields: &RAYMARCH_COST_FIELDS,
+        add: |app, params| {
+            app.add_plugins((
+                RaymarchCostPlugin {
+                    config: RaymarchCostConfig::from_params(
+                        params,
+                    ),
+                },
+                MaterialParamsPlugin::<RaymarchCostMaterial>::new(
+                    raymarch_cost::ID,
+                    params,
+                    &RAYMARCH_COST_FIELDS,
+                ),
+            ));
+        },
+    },
+    Example {
+        id: playground::ID,
+        title: "Shader playground",
+        description: "Renders any WGSL fragment shader against the standard uniform block. Drop a .wgsl file onto the window to switch shaders.",
+        params: &[Param {
+            name: "shader",
+            kind: ParamKind::Path,
+            default: "playground.wgsl",
+            description: "The fragment shader to load.",
+        }],
+        fields: &[],
+        add: |app, params| {
+            app.add_plugins(PlaygroundPlugin {
+                config: PlaygroundConfig {
+                    shader: params.get("shader"),
+                },
+            });
+        },
+    },
+];
+
+pub fn find(id: &str) -> Option<&'static Example> {
+    EXAMPLES.iter().find(|example| example.id == id)
+}
+
+impl Example {
+    pub fn live_demo_url(&self) -> String {
+        format!("{LIVE_DEMO_URL}?example={}", self.id)
+    }
+}
+
+/// The markdown list of live demos in the README.
+pub fn readme_list() -> String {
+    EXAMPLES
+        .iter()
+        .map(|example| {
+            format!(
+                "* [{}]({}): {}",
+                example.id,
+                example.live_demo_url(),
+                example.description
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The page shown by the web build when no example is
+/// selected.
+pub fn index_html() -> String {
+    let items = EXAMPLES
+        .iter()
+        .map(|example| {
+            let params = example
+                .params
+                .iter()
+                .map(|param| {
+                    format!(
+                        "<li><code>{}={}</code> (default <code>{}</code>): {}</li>",
+                        param.name,
+                        param.kind.describe(),
+                        param.default,
+                        param.description
+                    )
+                })
+                .collect::<String>();
+            format!(
+                r#"<li><a href="?example={}">{}</a><p>{}</p><ul>{params}</ul></li>"#,
+                example.id, example.title, example.description
+            )
+        })
+        .collect::<String>();
+    let view_params = VIEW_PARAMS
+        .iter()
+        .map(|param| {
+            format!(
+                "<li><code>{}={}</code>: {}</li>",
+                param.name,
+                param.kind.describe(),
+                param.description
+            )
+        })
+        .collect::<String>();
+    format!(
+        r#"<main class="index"><h1>SDF and raymarching examples</h1><ul>{items}</ul><p>Every example also takes:</p><ul>{view_params}</ul></main>"#
+    )
+}
@@ -0,0 +1,388 @@
+//! CPU versions of the signed distance functions in
+//! `sdf.wgsl`, so scenes can be evaluated outside of a
+//! shader (in the CPU renderer, for gizmos, in tests).
+//!
+//! Each function mirrors its WGSL counterpart line for line,
+//! including WGSL's `sign` and `round` semantics, so the CPU
+//! and GPU pictures agree.
+
+use bevy::prelude::*;
+
+/// Anything that can report the signed distance from a
+/// point to its surface: negative inside, positive
+/// outside.
+pub trait Sdf {
+    fn distance(&self, point: Vec2) -> f32;
+
+    /// The gradient of the distance at `point`, by central
+    /// differences. Where the distance is exact it has a
+    /// length of 1, pointing away from the surface outside
+    /// the shape and towards it inside.
+    fn gradient(&self, point: Vec2) -> Vec2 {
+        let x = Vec2::X * GRADIENT_STEP;
+        let y = Vec2::Y * GRADIENT_STEP;
+        Vec2::new(
+            self.distance(point + x)
+                - self.distance(point - x),
+            self.distance(point + y)
+                - self.distance(point - y),
+        ) / (2. * GRADIENT_STEP)
+    }
+
+    /// The point on the surface closest to `point`, found
+    /// by [`closest_point_by_gradient`] unless the shape
+    /// knows it exactly.
+    fn closest_point(&self, point: Vec2) -> Vec2 {
+        closest_point_by_gradient(self, point)
+    }
+}
+
+/// How far apart [`Sdf::gradient`] samples the distance.
+pub const GRADIENT_STEP: f32 = 0.0005;
+
+/// Walks from `point` to the surface of `sdf` along the
+/// gradient, Newton style, so an exact distance gets there
+/// in one step and a bound in a few. For a bound the point
+/// found is on the surface but not always the closest.
+pub fn closest_point_by_gradient<S: Sdf + ?Sized>(
+    sdf: &S,
+    point: Vec2,
+) -> Vec2 {
+    let mut point = point;
+    for _ in 0..8 {
+        let distance = sdf.distance(point);
+        let gradient = sdf.gradient(point);
+        let length_squared = gradient.length_squared();
+        if distance.abs() < 1e-6 || length_squared == 0. {
+            break;
+        }
+        point -= gradient * distance / length_squared;
+    }
+    point
+}
+
+impl<F: Fn(Vec2) -> f32> Sdf for F {
+    fn distance(&self, point: Vec2) -> f32 {
+        self(point)
+    }
+}
+
+/// The shapes the gradient examples switch between. The
+/// discriminants are the ids `shapes()` in `sdf.wgsl`
+/// expects in the `shape` uniform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shape {
+    Circle = 1,
+    Box = 2,
+    EquilateralTriangle = 3,
+    X = 4,
+    CoolS = 5,
+}
+
+impl Shape {
+    pub const ALL: [Shape; 5] = [
+        Shape::Circle,
+        Shape::Box,
+        Shape::EquilateralTriangle,
+        Shape::X,
+        Shape::CoolS,
+    ];
+
+    /// The names used for shapes in parameters and on the
+    /// command line, in the same order as [`Shape::ALL`].
+    pub const NAMES: [&'static str; 5] =
+        ["circle", "box", "triangle", "x", "cool-s"];
+
+    pub fn as_u32(&self) -> u32 {
+        *self as u32
+    }
+
+    pub fn name(&self) -> &'static str {
+        Shape::NAMES[*self as usize - 1]
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Shape::ALL
+            .into_iter()
+            .find(|shape| shape.name() == name)
+    }
+
+    /// The next shape in [`Shape::ALL`], wrapping around.
+    pub fn next(&self) -> Self {
+        Shape::ALL[*self as usize % Shape::ALL.len()]
+    }
+
+    /// The inverse of [`Shape::as_u32`]. Like the shader,
+    /// unknown ids fall back to the circle.
+    pub fn from_u32(id: u32) -> Self {
+        Shape::ALL
+            .into_iter()
+            .find(|shape| shape.as_u32() == id)
+            .unwrap_or(Shape::Circle)
+    }
+}
+
+impl Sdf for Shape {
+    /// The same shapes, at the same sizes, as `shapes()` in
+    /// `sdf.wgsl`.
+    fn distance(&self, coord: Vec2) -> f32 {
+        match self {
+            Shape::Circle => {
+                sd_circle(coord, Vec2::ZERO, 0.5)
+            }
+            Shape::Box => sd_box(coord, Vec2::splat(0.5)),
+            Shape::EquilateralTriangle => {
+                sd_equilateral_triangle(coord, 0.5)
+            }
+            Shape::X => sd_rounded_x(coord, 0.7, 0.1),
+            Shape::CoolS => sdf_cool_s(coord),
+        }
+    }
+
+    /// Exact for every shape but the cool S, which walks
+    /// the gradient.
+    fn closest_point(&self, coord: Vec2) -> Vec2 {
+        match self {
+            Shape::Circle => {
+                closest_point_circle(coord, Vec2::ZERO, 0.5)
+            }
+            Shape::Box => {
+                closest_point_box(coord, Vec2::splat(0.5))
+            }
+            Shape::EquilateralTriangle => {
+                closest_point_equilateral_triangle(
+                    coord, 0.5,
+                )
+            }
+            Shape::X => {
+                closest_point_rounded_x(coord, 0.7, 0.1)
+            }
+            Shape::CoolS => {
+                closest_point_by_gradient(self, coord)
+            }
+        }
+    }
+}
+
+/// WGSL's `sign`, which (unlike `f32::signum`) is zero at
+/// zero.
+fn sign(x: f32) -> f32 {
+    if x > 0.0 {
+        1.0
+    } else if x < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+pub fn sd_circle(
+    p: Vec2,
+    center: Vec2,
+    radius: f32,
+) -> f32 {
+    (p - center).length() - radius
+}
+
+pub fn rounded_box(p: Vec2, b: Vec2, r: Vec4) -> f32 {
+    let mut new_r = r;
+    if p.x <= 0.0 {
+        new_r.x = r.z;
+        new_r.y = r.w;
+    }
+    if p.y <= 0.0 {
+        new_r.x = r.y;
+    }
+    let q = p.abs() - b + new_r.x;
+    q.x.max(q.y).min(0.0) + q.max(Vec2::ZERO).length()
+        - new_r.x
+}
+
+pub fn sd_box(p: Vec2, b: Vec2) -> f32 {
+    let d = p.abs() - b;
+    d.max(Vec2::ZERO).length() + d.x.max(d.y).min(0.0)
+}
+
+pub fn sd_equilateral_triangle(point: Vec2, r: f32) -> f32 {
+    let mut p = point;
+    let k = 3.0_f32.sqrt();
+    p.x = p.x.abs() - r;
+    p.y += r / k;
+    if p.x + k * p.y > 0.0 {
+        p = Vec2::new(p.x - k * p.y, -k * p.x - p.y) / 2.0;
+    }
+    p.x -= p.x.clamp(-2.0 * r, 0.0);
+    -p.length() * sign(p.y)
+}
+
+/// Polynomial smooth minimum: blends `a` and `b` over a
+/// distance of `k`.
+pub fn smin(a: f32, b: f32, k: f32) -> f32 {
+    let h = (k - (a - b).abs()).max(0.0);
+    a.min(b) - h * h * 0.25 / k
+}
+
+pub fn sd_triangle(
+    p: Vec2,
+    p0: Vec2,
+    p1: Vec2,
+    p2: Vec2,
+) -> f32 {
+    let e0 = p1 - p0;
+    let e1 = p2 - p1;
+    let e2 = p0 - p2;
+    let v0 = p - p0;
+    let v1 = p - p1;
+    let v2 = p - p2;
+    let pq0 =
+        v0 - e0 * (v0.dot(e0) / e0.dot(e0)).clamp(0.0, 1.0);
+    let pq1 =
+        v1 - e1 * (v1.dot(e1) / e1.dot(e1)).clamp(0.0, 1.0);
+    let pq2 =
+        v2 - e2 * (v2.dot(e2) / e2.dot(e2)).clamp(0.0, 1.0);
+    let s = sign(e0.x * e2.y - e0.y * e2.x);
+    let d = Vec2::new(
+        pq0.dot(pq0),
+        s * (v0.x * e0.y - v0.y * e0.x),
+    )
+    .min(Vec2::new(
+        pq1.dot(pq1),
+        s * (v1.x * e1.y - v1.y * e1.x),
+    ))
+    .min(Vec2::new(
+        pq2.dot(pq2),
+        s * (v2.x * e2.y - v2.y * e2.x),
+    ));
+    -d.x.sqrt() * sign(d.y)
+}
+
+pub fn sd_rounded_x(p: Vec2, w: f32, r: f32) -> f32 {
+    let p = p.abs();
+    (p - (p.x + p.y).min(w) * 0.5).length() - r
+}
+
+pub fn sdf_cool_s(p: Vec2) -> f32 {
+    let mut p = p;
+    let six = if p.y < 0.0 { -p.x } else { p.x };
+    p.x = p.x.abs();
+    p.y = p.y.abs() - 0.2;
+    let rex = p.x - (p.x / 0.4).round_ties_even().min(0.4);
+    let aby = (p.y - 0.2).abs() - 0.6;
+
+    let mut d = dot_self(
+        Vec2::new(six, -p.y)
+            - (0.5 * (six - p.y)).clamp(0.0, 0.2),
+    );
+    d = d.min(dot_self(
+        Vec2::new(p.x, -aby)
+            - (0.5 * (p.x - aby)).clamp(0.0, 0.4),
+    ));
+    d = d.min(dot_self(Vec2::new(
+        rex,
+        p.y - p.y.clamp(0.0, 0.4),
+    )));
+
+    let s = 2.0 * p.x + aby + (aby + 0.4).abs() - 0.4;
+    d.sqrt() * sign(s)
+}
+
+pub fn dot_self(v: Vec2) -> f32 {
+    v.dot(v)
+}
+
+/// The closest point on the edge of [`sd_circle`]. The
+/// centre itself goes to the right.
+pub fn closest_point_circle(
+    p: Vec2,
+    center: Vec2,
+    radius: f32,
+) -> Vec2 {
+    center
+        + (p - center).try_normalize().unwrap_or(Vec2::X)
+            * radius
+}
+
+/// The closest point on the edge of [`sd_box`]: `p`
+/// clamped to the box outside, moved to the nearest side
+/// inside.
+pub fn closest_point_box(p: Vec2, b: Vec2) -> Vec2 {
+    let d = p.abs() - b;
+    if d.x > 0.0 || d.y > 0.0 {
+        return p.clamp(-b, b);
+    }
+    if d.x > d.y {
+        Vec2::new(b.x.copysign(p.x), p.y)
+    } else {
+        Vec2::new(p.x, b.y.copysign(p.y))
+    }
+}
+
+/// The closest point on the edges of
+/// [`sd_equilateral_triangle`], whose corners are `r` to
+/// either side of the middle and `2r/√3` below it.
+pub fn closest_point_equilateral_triangle(
+    p: Vec2,
+    r: f32,
+) -> Vec2 {
+    let k = 3.0_f32.sqrt();
+    let corners = [
+        Vec2::new(-r, -r / k),
+        Vec2::new(r, -r / k),
+        Vec2::new(0.0, 2.0 * r / k),
+    ];
+    (0..3)
+        .map(|i| {
+            closest_point_segment(
+                p,
+                corners[i],
+                corners[(i + 1) % 3],
+            )
+        })
+        .min_by(|a, b| {
+            a.distance_squared(p)
+                .total_cmp(&b.distance_squared(p))
+        })
+        .expect("a triangle has edges")
+}
+
+/// The closest point on the edge of [`sd_rounded_x`]: `r`
+/// out from the closest point on the diagonals, which end
+/// at `(w/2, w/2)` and its mirror images. Near the middle
+/// that lands inside the other diagonal's arm, so the
+/// closest point is the corner between the two arms.
+pub fn closest_point_rounded_x(
+    p: Vec2,
+    w: f32,
+    r: f32,
+) -> Vec2 {
+    let mirror = Vec2::new(
+        1.0_f32.copysign(p.x),
+        1.0_f32.copysign(p.y),
+    );
+    let q = p.abs();
+    let spine = Vec2::splat((q.x + q.y).min(w) * 0.5);
+    let out = (q - spine)
+        .try_normalize()
+        .unwrap_or(Vec2::new(1.0, -1.0).normalize());
+    let edge = spine + out * r;
+    let corner = r * 2.0_f32.sqrt();
+    let edge = if edge.y < 0.0 {
+        Vec2::new(corner, 0.0)
+    } else if edge.x < 0.0 {
+        Vec2::new(0.0, corner)
+    } else {
+        edge
+    };
+    edge * mirror
+}
+
+/// The closest point to `p` on the segment from `a` to
+/// `b`.
+pub fn closest_point_segment(
+    p: Vec2,
+    a: Vec2,
+    b: Vec2,
+) -> Vec2 {
+    let ab = b - a;
+    a + ab * ((p - a).dot(ab) / ab.dot(ab)).clamp(0.0, 1.0)
+}
@@ -0,0 +1,288 @@
+#define_import_path yt_raymarch_2d::sdf
+
+// center_sdf moves the coordinate system from uv==-0.5..0.5
+// to x: -0.7..0.7, y: -0.5..0.5
+// This makes it so that the coordinate system is a square from
+// -0.5..0.5 in both directions.
+//
+// This is useful, especially when rendering SDFs that need to be
+// proportional regardless of window aspect_ratio
+fn center_sdf(uv: vec2f, width_height: vec2f) -> vec2f {
+    let viewport_width = width_height.x;
+    let viewport_height = width_height.y;
+    // one side is probably going to be shorter than the other
+    let min_viewport_size = min(viewport_width, viewport_height);
+    let max_viewport_size = max(viewport_width, viewport_height);
+
+    // the adjustment by which we need to reposition the longer side
+    // for the shader result to still be centered
+    let distance_to_push_center = (max_viewport_size - min_viewport_size) / 2.0;
+    let center_push = distance_to_push_center / min_viewport_size * 2.0;
+
+    var coord = (uv * vec2(viewport_width, viewport_height) / min_viewport_size * 2.0) - 1.0;
+    if viewport_width > viewport_height {
+        coord.x -= center_push;
+    } else if viewport_width < viewport_height {
+        coord.y -= center_push;
+    };
+
+    return coord;
+}
+
+// distance_color colours a distance like the gradient examples,
+// see `color_map.rs`. The distance is scaled to -1..1, reaching
+// the ends at `range` and spreading out the small distances with
+// `log_scale`. Map 0 then fades from `background` at the surface
+// to `inside` and `outside`, the other maps run from inside to
+// outside through their own colours.
+fn distance_color(
+    distance: f32,
+    inside: vec4f,
+    outside: vec4f,
+    background: vec4f,
+    map: u32,
+    range: f32,
+    log_scale: u32
+) -> vec4f {
+    var magnitude = abs(distance) / max(range, 0.000001);
+    if log_scale != 0u {
+        // matches LOG_BASE in color_map.rs
+        magnitude = log(1.0 + 99.0 * magnitude) / log(100.0);
+    }
+    let position = min(magnitude, 1.0) * select(1.0, -1.0, distance < 0.);
+
+    if map == 0u {
+        if position >= 0. {
+            return mix(background, outside, position);
+        }
+        return mix(background, inside, -position);
+    }
+    return vec4(color_ramp(map, position * 0.5 + 0.5), 1.0);
+}
+
+// five evenly spaced sRGB stops per colour map, in the order of
+// `ColorMap::NAMES`
+fn color_ramp(map: u32, t: f32) -> vec3f {
+    var stops: array<vec3f, 5>;
+    switch map {
+        // diverging
+        case 1u: {
+            stops = array<vec3f, 5>(
+                vec3(0.231, 0.298, 0.753),
+                vec3(0.553, 0.690, 0.996),
+                vec3(0.867, 0.867, 0.867),
+                vec3(0.957, 0.604, 0.482),
+                vec3(0.706, 0.016, 0.149),
+            );
+        }
+        // viridis
+        case 2u: {
+            stops = array<vec3f, 5>(
+                vec3(0.267, 0.005, 0.329),
+                vec3(0.231, 0.322, 0.545),
+                vec3(0.129, 0.569, 0.549),
+                vec3(0.369, 0.788, 0.384),
+                vec3(0.992, 0.906, 0.145),
+            );
+        }
+        // cividis
+        case 3u: {
+            stops = array<vec3f, 5>(
+                vec3(0.000, 0.133, 0.306),
+                vec3(0.255, 0.302, 0.420),
+                vec3(0.486, 0.482, 0.471),
+                vec3(0.737, 0.686, 0.435),
+                vec3(0.996, 0.910, 0.220),
+            );
+        }
+        // greyscale
+        default: {
+            stops = array<vec3f, 5>(
+                vec3(0.00),
+                vec3(0.25),
+                vec3(0.50),
+                vec3(0.75),
+                vec3(1.00),
+            );
+        }
+    }
+    let x = clamp(t, 0., 1.) * 4.0;
+    let i = min(u32(x), 3u);
+    let srgb = mix(stops[i], stops[i + 1u], x - f32(i));
+    // the swapchain expects linear colours
+    return select(
+        pow((srgb + 0.055) / 1.055, vec3(2.4)),
+        srgb / 12.92,
+        srgb <= vec3(0.04045)
+    );
+}
+
+// draw_contours draws isolines over `color`, like Inigo
+// Quilez's distance pictures. `settings` is the material's
+// `contours` uniform:
+//   x: the distance between lines, 0 turns them off
+//   y: the line width in pixels
+//   z: every how many lines one is a major line, 0 for none
+//   w: how quickly the colour fades towards the surface, 0
+//      for not at all
+// `width` is the distance one pixel covers, usually
+// `fwidth(distance)` taken before any branching.
+fn draw_contours(color: vec4f, distance: f32, width: f32, settings: vec4f) -> vec4f {
+    let spacing = settings.x;
+    if spacing <= 0. {
+        return color;
+    }
+    let thickness = settings.y;
+    let major = settings.z;
+    let width = max(width, 0.000001);
+
+    var rgb = color.rgb;
+    if settings.w > 0. {
+        rgb *= 1.0 - exp(-settings.w * abs(distance));
+    }
+    // black lines over light colours, white over dark ones
+    let luminance = dot(rgb, vec3(0.2126, 0.7152, 0.0722));
+    let ink = select(vec3(0.), vec3(1.), luminance < 0.18);
+    rgb = mix(rgb, ink, 0.4 * contour_line(nearest_line(distance, spacing), thickness, width));
+    if major > 0. {
+        rgb = mix(rgb, ink, 0.8 * contour_line(nearest_line(distance, spacing * major), thickness * 1.5, width));
+    }
+    // the surface itself
+    rgb = mix(rgb, vec3(1.), contour_line(abs(distance), thickness * 2.0, width));
+    return vec4(rgb, color.a);
+}
+
+// how far `distance` is from the closest multiple of `spacing`
+fn nearest_line(distance: f32, spacing: f32) -> f32 {
+    let offset = distance / spacing + 0.5;
+    return abs(offset - floor(offset) - 0.5) * spacing;
+}
+
+// how much of a pixel a line `thickness` pixels wide covers,
+// `offset` away from its middle
+fn contour_line(offset: f32, thickness: f32, width: f32) -> f32 {
+    return clamp(thickness * 0.5 - offset / width + 0.5, 0., 1.);
+}
+
+// shapes picks one of the demo shapes by the same id the
+// Rust side uses for `CurrentShape`
+fn shapes(shape: u32, coord: vec2f) -> f32 {
+      switch shape {
+        case 2u: {
+          return sd_box(coord, vec2f(0.5,0.5));
+        }
+        case 3u: {
+          return sd_equilateral_triangle(coord, 0.5);
+        }
+        case 4u: {
+            return sd_rounded_x(coord, 0.7, 0.1);
+        }
+        case 5u {
+            return sdf_cool_s(coord);
+        }
+        // Lone default
+        default: {
+          return sd_circle(coord, vec2f(0.,0.), 0.5);
+        }
+    }
+}
+
+fn sd_circle(p: vec2f, center: vec2f, radius: f32) -> f32 {
+    return length(p-center) - radius;
+}
+
+fn rounded_box(p: vec2f, b: vec2f, r: vec4f) -> f32 {
+    var new_r = r;
+    if (p.x <= 0.0) {
+        new_r.x = r.z;
+        new_r.y = r.w;
+    };
+    if (p.y <= 0.0) {
+        new_r.x = r.y;
+    };
+    let q = abs(p)-b+new_r.x;
+    return min(max(q.x,q.y),0.0) + length(max(q,vec2(0.0))) - new_r.x;
+}
+
+// fn sdArc( in vec2 p, in vec2 sc, in float ra, float rb ) -> f32
+// {
+//     // sc is the sin/cos of the arc's aperture
+//     p.x = abs(p.x);
+//     return ((sc.y*p.x>sc.x*p.y) ? length(p-sc*ra) :
+//                                   abs(length(p)-ra)) - rb;
+// }
+
+fn sd_box( p: vec2f, b: vec2f ) -> f32
+{
+    let d = abs(p) - b;
+    return length(max(d,vec2(0.0))) + min(max(d.x,d.y),0.0);
+}
+
+fn sd_equilateral_triangle( point: vec2f, r: f32 ) -> f32
+{
+    var p = point;
+    let k: f32 = sqrt(3.0);
+    p.x = abs(p.x) - r;
+    p.y = p.y + r/k;
+    if( p.x+k*p.y>0.0 ) {
+        p = vec2(p.x-k*p.y,-k*p.x-p.y)/2.0;
+    }
+    p.x -= clamp( p.x, -2.0*r, 0.0 );
+    return -length(p)*sign(p.y);
+}
+
+fn smin( a: f32, b: f32, k: f32 ) -> f32
+{
+    let h = max(k-abs(a-b),0.0);
+    return min(a, b) - h*h*0.25/k;
+}
+
+fn sd_triangle( p: vec2f, p0: vec2f, p1: vec2f, p2: vec2f ) -> f32
+{
+    let e0: vec2f = p1-p0;
+    let e1: vec2f = p2-p1;
+    let e2: vec2f = p0-p2;
+    let v0: vec2f = p -p0;
+    let v1: vec2f = p -p1;
+    let v2: vec2f = p -p2;
+    let pq0: vec2f = v0 - e0*clamp( dot(v0,e0)/dot(e0,e0), 0.0, 1.0 );
+    let pq1: vec2f = v1 - e1*clamp( dot(v1,e1)/dot(e1,e1), 0.0, 1.0 );
+    let pq2: vec2f = v2 - e2*clamp( dot(v2,e2)/dot(e2,e2), 0.0, 1.0 );
+    let s: f32 = sign( e0.x*e2.y - e0.y*e2.x );
+    let d: vec2f = min(min(vec2(dot(pq0,pq0), s*(v0.x*e0.y-v0.y*e0.x)),
+                     vec2(dot(pq1,pq1), s*(v1.x*e1.y-v1.y*e1.x))),
+                     vec2(dot(pq2,pq2), s*(v2.x*e2.y-v2.y*e2.x)));
+    return -sqrt(d.x)*sign(d.y);
+}
+
+fn sd_rounded_x( p: vec2f, w: f32, r: f32 ) -> f32
+{
+    let p = abs(p);
+    return length(p-min(p.x+p.y,w)*0.5) - r;
+}
+
+fn sdf_cool_s( p: vec2f ) -> f32
+{
+    var p = p;
+    var six: f32 = 0.;
+    if (p.y<0.0) {
+        six = -p.x;
+    } else {
+        six = p.x;
+    }
+    p.x = abs(p.x);
+    p.y = abs(p.y) - 0.2;
+    let rex: f32 = p.x - min(round(p.x/0.4),0.4);
+    let aby: f32 = abs(p.y - 0.2) - 0.6;
+
+    var d: f32 = dot_self(vec2(six,-p.y)-clamp(0.5*(six-p.y),0.0,0.2));
+    d = min(d,   dot_self(vec2(p.x,-aby)-clamp(0.5*(p.x-aby),0.0,0.4)));
+    d = min(d,   dot_self(vec2(rex,p.y  -clamp(p.y          ,0.0,0.4))));
+
+    let s: f32 = 2.0*p.x + aby + abs(aby+0.4) - 0.4;
+    return sqrt(d) * sign(s);
+}
+
+// usually called dot2, but naga_oil rejects module identifiers
+// that end in a digit
+fn dot_self( v: vec2f ) -> f32 { return dot(v,v); }
@@ -0,0 +1,623 @@
+//! Scripted takes for recording: a storyboard lists timed
+//! keyframes for the mouse, the shape, parameter values,
+//! the camera, the step-through position and captions,
+//! and a player interpolates between them.
+//!
+//! Storyboards are written in RON:
+//!
+//! ```ron
+//! Storyboard(
+//!     example: "single-sdf-distance-as-gradient-with-algorithm",
+//!     keyframes: [
+//!         (time: 0.0, mouse: Some((0.5, 0.5)), caption: Some("The box SDF")),
+//!         (time: 2.0, mouse: Some((0.9, 0.2)), ease: Smooth),
+//!         (time: 3.0, shape: Some("cool-s"), zoom: Some(2.0), caption: Some("")),
+//!     ],
+//! )
+//! ```
+//!
+//! The player advances by one frame of `1 / fps` seconds
+//! per app update, however long the update really took,
+//! so the same storyboard always plays out the same way.
+
+use std::{collections::BTreeMap, fmt};
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    bevy_plugin_shader2d::{
+        set_camera_center, CompareCamera, SdfMouse,
+    },
+    gradient::CurrentShape,
+    material_params::MaterialPanel,
+    params::{ParamError, Params},
+    registry,
+    sdf::Shape,
+    switcher::ActiveExample,
+};
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Storyboard {
+    /// The example the take starts with, as in
+    /// `?example=`. Left out for apps that don't run the
+    /// examples, which only play the step and captions.
+    #[serde(default)]
+    pub example: String,
+    /// The frame rate the take is recorded at.
+    #[serde(default = "default_fps")]
+    pub fps: f32,
+    pub keyframes: Vec<Keyframe>,
+}
+
+fn default_fps() -> f32 {
+    60.
+}
+
+/// Everything a keyframe leaves out keeps its value from
+/// the keyframes around it.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default)]
+pub struct Keyframe {
+    /// Seconds from the start of the take.
+    pub time: f32,
+    /// Switches to another example.
+    pub example: Option<String>,
+    /// The [`SdfMouse`] position, which stays pinned while
+    /// the storyboard plays.
+    pub mouse: Option<(f32, f32)>,
+    /// As in the `zoom` parameter.
+    pub zoom: Option<f32>,
+    /// As in the `center` parameter.
+    pub center: Option<(f32, f32)>,
+    /// How many raymarching steps are shown, see
+    /// [`StepThrough`].
+    pub step: Option<f32>,
+    /// One of [`Shape::NAMES`].
+    pub shape: Option<String>,
+    /// Values for the example's material parameters, eg:
+    /// `{"thickness": "0.02"}`.
+    pub params: BTreeMap<String, String>,
+    /// Shown at the bottom of the window. An empty caption
+    /// hides it.
+    pub caption: Option<String>,
+    /// How the mouse, camera and step get to this
+    /// keyframe's values from the previous ones.
+    pub ease: Ease,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Default, Deserialize,
+)]
+pub enum Ease {
+    #[default]
+    Linear,
+    /// Starts and stops gently.
+    Smooth,
+    /// Jumps to the value at the keyframe.
+    Step,
+}
+
+impl Ease {
+    fn apply(&self, t: f32) -> f32 {
+        match self {
+            Ease::Linear => t,
+            Ease::Smooth => t * t * (3. - 2. * t),
+            Ease::Step => {
+                if t < 1. {
+                    0.
+                } else {
+                    1.
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum StoryboardError {
+    Parse(ron::error::SpannedError),
+    /// The frame rate isn't a number above 0.
+    Fps(f32),
+    /// A keyframe's values aren't valid for the example
+    /// running at that time.
+    Keyframe {
+        time: f32,
+        errors: Vec<ParamError>,
+    },
+    /// A keyframe sets a parameter that only applies when
+    /// the example starts, rather than a material field.
+    Fixed {
+        time: f32,
+        name: String,
+    },
+}
+
+impl fmt::Display for StoryboardError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            StoryboardError::Parse(error) => {
+                write!(
+                    f,
+                    "the storyboard can't be read: {error}"
+                )
+            }
+            StoryboardError::Fps(fps) => {
+                write!(
+                    f,
+                    "the frame rate has to be above 0, not {fps}"
+                )
+            }
+            StoryboardError::Fixed { time, name } => {
+                write!(
+                    f,
+                    "in the keyframe at {time}s: `{name}` can't change while the example runs"
+                )
+            }
+            StoryboardError::Keyframe { time, errors } => {
+                write!(f, "in the keyframe at {time}s: ")?;
+                let errors = errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                write!(f, "{}", errors.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for StoryboardError {}
+
+/// The values at one point in the take.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Frame {
+    pub example: String,
+    pub mouse: Option<Vec2>,
+    pub zoom: Option<f32>,
+    pub center: Option<Vec2>,
+    pub step: Option<f32>,
+    pub shape: Option<Shape>,
+    pub params: BTreeMap<String, String>,
+    pub caption: String,
+}
+
+/// Values the player interpolates.
+trait Mix: Copy {
+    fn mix(self, other: Self, t: f32) -> Self;
+}
+
+impl Mix for f32 {
+    fn mix(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Mix for Vec2 {
+    fn mix(self, other: Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+impl Storyboard {
+    /// Reads a storyboard, checking every keyframe against
+    /// the parameters of the example it applies to.
+    pub fn from_ron(
+        text: &str,
+    ) -> Result<Self, StoryboardError> {
+        let mut storyboard: Storyboard =
+            ron::from_str(text)
+                .map_err(StoryboardError::Parse)?;
+        // also rules out NaN, which would never advance
+        if !(storyboard.fps > 0.
+            && storyboard.fps.is_finite())
+        {
+            return Err(StoryboardError::Fps(
+                storyboard.fps,
+            ));
+        }
+        // stable, so keyframes at the same time apply in
+        // the order they're written
+        storyboard
+            .keyframes
+            .sort_by(|a, b| a.time.total_cmp(&b.time));
+        let mut example = storyboard.example.clone();
+        for keyframe in &storyboard.keyframes {
+            if let Some(other) = &keyframe.example {
+                example = other.clone();
+            }
+            keyframe.check(&example)?;
+        }
+        Ok(storyboard)
+    }
+
+    /// The parameters the app starts with.
+    pub fn params(&self) -> Params {
+        let mut params = Params::default();
+        params.insert(
+            "example".to_string(),
+            Some(self.example.clone()),
+        );
+        params
+    }
+
+    /// When the last keyframe is.
+    pub fn duration(&self) -> f32 {
+        self.keyframes
+            .last()
+            .map_or(0., |keyframe| keyframe.time)
+    }
+
+    /// The values at `time`, in seconds from the start.
+    pub fn sample(&self, time: f32) -> Frame {
+        let past = || {
+            self.keyframes.iter().take_while(
+                move |keyframe| keyframe.time <= time,
+            )
+        };
+        let mut params = BTreeMap::new();
+        for keyframe in past() {
+            params.extend(keyframe.params.clone());
+        }
+        let vec2 =
+            |value: (f32, f32)| Vec2::new(value.0, value.1);
+        Frame {
+            example: past()
+                .filter_map(|keyframe| {
+                    keyframe.example.clone()
+                })
+                .last()
+                .unwrap_or_else(|| self.example.clone()),
+            mouse: self.track(time, |keyframe| {
+                keyframe.mouse.map(vec2)
+            }),
+            zoom: self
+                .track(time, |keyframe| keyframe.zoom),
+            center: self.track(time, |keyframe| {
+                keyframe.center.map(vec2)
+            }),
+            step: self
+                .track(time, |keyframe| keyframe.step),
+            shape: past()
+                .filter_map(|keyframe| {
+                    keyframe.shape.as_deref()
+                })
+                .last()
+                .and_then(Shape::from_name),
+            params,
+            caption: past()
+                .filter_map(|keyframe| {
+                    keyframe.caption.clone()
+                })
+                .last()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Interpolates between the keyframes around `time`
+    /// that set a value. Before the first one, it holds
+    /// the first value.
+    fn track<T: Mix>(
+        &self,
+        time: f32,
+        value: impl Fn(&Keyframe) -> Option<T>,
+    ) -> Option<T> {
+        let before = self
+            .keyframes
+            .iter()
+            .filter(|keyframe| keyframe.time <= time)
+            .filter_map(|keyframe| {
+                Some((keyframe.time, value(keyframe)?))
+            })
+            .last();
+        let after =
+            self.keyframes.iter().find_map(|keyframe| {
+                if keyframe.time <= time {
+                    return None;
+                }
+                Some((keyframe, value(keyframe)?))
+            });
+        match (before, after) {
+            (Some((start, from)), Some((keyframe, to))) => {
+                let t = (time - start)
+                    / (keyframe.time - start);
+                Some(from.mix(to, keyframe.ease.apply(t)))
+            }
+            (Some((_, value)), None) => Some(value),
+            (None, Some((_, value))) => Some(value),
+            (None, None) => None,
+        }
+    }
+}
+
+impl Keyframe {
+    /// Checks the values against `example`'s parameters.
+    /// Only material fields can be played, see
+    /// `play_params`.
+    fn check(
+        &self,
+        example: &str,
+    ) -> Result<(), StoryboardError> {
+        let invalid = |errors| StoryboardError::Keyframe {
+            time: self.time,
+            errors,
+        };
+        let fields = if example.is_empty() {
+            &[][..]
+        } else {
+            let example = registry::find(example)
+                .ok_or_else(|| {
+                    invalid(vec![
+                        ParamError::UnknownExample(
+                            example.to_string(),
+                        ),
+                    ])
+                })?;
+            let mut params = Params::default();
+            if let Some(shape) = &self.shape {
+                params.insert(
+                    "shape".to_string(),
+                    Some(shape.clone()),
+                );
+            }
+            for (name, value) in &self.params {
+                params.insert(
+                    name.clone(),
+                    Some(value.clone()),
+                );
+            }
+            params.validate(example).map_err(invalid)?;
+            example.fields
+        };
+        match self
+            .params
+            .keys()
+            .find(|name| !fields.contains(&name.as_str()))
+        {
+            Some(name) => Err(StoryboardError::Fixed {
+                time: self.time,
+                name: name.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// How many steps of a raymarch are shown, for examples
+/// that step through one. Set by the storyboard's `step`.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct StepThrough(pub f32);
+
+/// Plays `storyboard` from the start of the app.
+pub struct StoryboardPlugin {
+    pub storyboard: Storyboard,
+}
+
+impl Plugin for StoryboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(StoryboardPlayer {
+            storyboard: self.storyboard.clone(),
+            frame: 0,
+        })
+        .insert_resource(CurrentFrame(
+            self.storyboard.sample(0.),
+        ))
+        .add_systems(Startup, spawn_caption)
+        .add_systems(
+            Update,
+            (
+                advance,
+                (
+                    play_example,
+                    play_mouse,
+                    play_camera,
+                    play_step,
+                    play_shape,
+                    play_params,
+                    play_caption,
+                ),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// The storyboard being played, and how far along it is.
+#[derive(Resource)]
+pub struct StoryboardPlayer {
+    pub storyboard: Storyboard,
+    pub frame: u32,
+}
+
+/// The values for the current frame.
+#[derive(Resource, Deref)]
+struct CurrentFrame(Frame);
+
+#[derive(Component)]
+struct Caption;
+
+fn spawn_caption(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 28.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(40.),
+            left: Val::Px(0.),
+            right: Val::Px(0.),
+            ..default()
+        })
+        .with_text_alignment(TextAlignment::Center)
+        .with_background_color(Color::rgba(
+            0., 0., 0., 0.6,
+        )),
+        Caption,
+    ));
+}
+
+fn advance(
+    mut player: ResMut<StoryboardPlayer>,
+    mut frame: ResMut<CurrentFrame>,
+) {
+    let time = player.frame as f32 / player.storyboard.fps;
+    frame.0 = player.storyboard.sample(time);
+    // the last frame is held once the take is over
+    if time < player.storyboard.duration() {
+        player.frame += 1;
+    }
+}
+
+fn play_example(
+    frame: Res<CurrentFrame>,
+    active: Option<Res<State<ActiveExample>>>,
+    next: Option<ResMut<NextState<ActiveExample>>>,
+) {
+    let (Some(active), Some(mut next)) = (active, next)
+    else {
+        return;
+    };
+    let Some(example) = registry::find(&frame.example)
+    else {
+        return;
+    };
+    if active.get().0 != example.id {
+        next.set(ActiveExample(example.id));
+    }
+}
+
+fn play_mouse(
+    frame: Res<CurrentFrame>,
+    mouse: Option<ResMut<SdfMouse>>,
+) {
+    let Some(mut mouse) = mouse else {
+        return;
+    };
+    let Some(position) = frame.mouse else {
+        return;
+    };
+    let played = SdfMouse {
+        position,
+        pinned: true,
+    };
+    if *mouse != played {
+        *mouse = played;
+    }
+}
+
+fn play_camera(
+    frame: Res<CurrentFrame>,
+    windows: Query<&Window>,
+    mut cameras: Query<
+        (&mut Transform, &mut OrthographicProjection),
+        (With<Camera2d>, Without<CompareCamera>),
+    >,
+) {
+    if frame.zoom.is_none() && frame.center.is_none() {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let half_window =
+        Vec2::new(window.width(), window.height()) / 2.;
+    for (mut transform, mut projection) in
+        cameras.iter_mut()
+    {
+        let scale = frame
+            .zoom
+            .map_or(projection.scale, |zoom| 1. / zoom);
+        if projection.scale != scale {
+            projection.scale = scale;
+        }
+        let center = frame.center.map_or(
+            transform.translation.truncate(),
+            |center| center * half_window,
+        );
+        let mut moved = *transform;
+        set_camera_center(
+            &mut moved, window, scale, center,
+        );
+        if moved != *transform {
+            *transform = moved;
+        }
+    }
+}
+
+fn play_step(
+    mut commands: Commands,
+    frame: Res<CurrentFrame>,
+    step: Option<Res<StepThrough>>,
+) {
+    let Some(played) = frame.step.map(StepThrough) else {
+        return;
+    };
+    if step.as_deref() != Some(&played) {
+        commands.insert_resource(played);
+    }
+}
+
+fn play_shape(
+    frame: Res<CurrentFrame>,
+    shape: Option<ResMut<CurrentShape>>,
+) {
+    let Some(mut shape) = shape else {
+        return;
+    };
+    if let Some(played) = frame.shape {
+        if shape.0 != played {
+            shape.0 = played;
+        }
+    }
+}
+
+/// Sets the values through the
+/// [material panel](crate::material_params), like
+/// changing them by hand would.
+fn play_params(
+    frame: Res<CurrentFrame>,
+    panel: Option<ResMut<MaterialPanel>>,
+) {
+    let Some(mut panel) = panel else {
+        return;
+    };
+    for (name, value) in &frame.params {
+        if panel.fields.contains(&name.as_str())
+            && panel.params.value(name)
+                != Some(value.as_str())
+        {
+            panel
+                .params
+                .insert(name.clone(), Some(value.clone()));
+        }
+    }
+}
+
+fn play_caption(
+    frame: Res<CurrentFrame>,
+    mut captions: Query<
+        (&mut Text, &mut Visibility),
+        With<Caption>,
+    >,
+) {
+    for (mut text, mut visibility) in captions.iter_mut() {
+        if text.sections[0].value != frame.caption {
+            text.sections[0].value = frame.caption.clone();
+        }
+        let shown = if frame.caption.is_empty() {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+        if *visibility != shown {
+            *visibility = shown;
+        }
+    }
+}
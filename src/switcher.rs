@@ -0,0 +1,288 @@
+//! Running several examples in one app and switching
+//! between them without reloading.
+//!
+//! Each example only runs its systems while
+//! [`ActiveExample`] is its id. Entering the state sets the
+//! example up, and leaving it despawns everything tagged
+//! with [`ExampleEntity`] and removes the example's
+//! materials and resources.
+
+use bevy::prelude::*;
+
+use crate::registry::{self, EXAMPLES};
+
+/// The id of the example that is running. The default, an
+/// empty id, runs none of them.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default,
+)]
+pub struct ActiveExample(pub &'static str);
+
+impl States for ActiveExample {
+    type Iter = std::vec::IntoIter<ActiveExample>;
+
+    fn variants() -> Self::Iter {
+        std::iter::once(ActiveExample::default())
+            .chain(
+                EXAMPLES.iter().map(|example| {
+                    ActiveExample(example.id)
+                }),
+            )
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+/// Marks the root entities an example spawns. They're
+/// despawned, with their children, when the example stops.
+#[derive(Component)]
+pub struct ExampleEntity;
+
+/// Adds the [`ActiveExample`] state, unless an earlier
+/// plugin already did.
+pub fn add_active_example_state(app: &mut App) {
+    if !app
+        .world
+        .contains_resource::<State<ActiveExample>>()
+    {
+        app.add_state::<ActiveExample>();
+    }
+}
+
+pub fn despawn_example_entities(
+    mut commands: Commands,
+    entities: Query<Entity, With<ExampleEntity>>,
+) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// A menu in the bottom right corner to pick an example,
+/// with buttons (and the Page Up/Page Down keys) for the
+/// previous and next one.
+pub struct ExampleSwitcherPlugin;
+
+impl Plugin for ExampleSwitcherPlugin {
+    fn build(&self, app: &mut App) {
+        add_active_example_state(app);
+        app.add_systems(Startup, setup_menu).add_systems(
+            Update,
+            (
+                switch_with_keys,
+                menu_buttons,
+                update_menu_title,
+            ),
+        );
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+enum MenuButton {
+    Previous,
+    Next,
+    ToggleList,
+    Example(&'static str),
+}
+
+#[derive(Component)]
+struct MenuTitle;
+
+#[derive(Component)]
+struct ExampleList;
+
+const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+
+fn setup_menu(mut commands: Commands) {
+    let text_style = TextStyle {
+        font_size: 16.0,
+        color: Color::rgb(0.9, 0.9, 0.9),
+        ..default()
+    };
+    let button = || ButtonBundle {
+        style: Style {
+            padding: UiRect::all(Val::Px(6.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        background_color: NORMAL_BUTTON.into(),
+        ..default()
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.),
+                bottom: Val::Px(10.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::End,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            display: Display::None,
+                            flex_direction:
+                                FlexDirection::Column,
+                            ..default()
+                        },
+                        background_color: Color::BLACK
+                            .into(),
+                        ..default()
+                    },
+                    ExampleList,
+                ))
+                .with_children(|list| {
+                    for example in EXAMPLES {
+                        list.spawn((
+                            button(),
+                            MenuButton::Example(example.id),
+                        ))
+                        .with_children(|button| {
+                            button.spawn(
+                                TextBundle::from_section(
+                                    example.title,
+                                    text_style.clone(),
+                                ),
+                            );
+                        });
+                    }
+                });
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    for (menu_button, label) in [
+                        (MenuButton::Previous, "<"),
+                        (MenuButton::ToggleList, ""),
+                        (MenuButton::Next, ">"),
+                    ] {
+                        row.spawn((
+                            button(),
+                            menu_button,
+                        ))
+                        .with_children(|button| {
+                            let mut label = button.spawn(
+                                TextBundle::from_section(
+                                    label,
+                                    text_style.clone(),
+                                ),
+                            );
+                            if let MenuButton::ToggleList =
+                                menu_button
+                            {
+                                label.insert(MenuTitle);
+                            }
+                        });
+                    }
+                });
+        });
+}
+
+/// The example `offset` places away from the active one in
+/// [`EXAMPLES`], wrapping around.
+fn neighbour(
+    active: &ActiveExample,
+    offset: isize,
+) -> ActiveExample {
+    let index = EXAMPLES
+        .iter()
+        .position(|example| example.id == active.0)
+        .unwrap_or(0) as isize;
+    let len = EXAMPLES.len() as isize;
+    ActiveExample(
+        EXAMPLES[(index + offset).rem_euclid(len) as usize]
+            .id,
+    )
+}
+
+fn switch_with_keys(
+    keys: Res<Input<KeyCode>>,
+    active: Res<State<ActiveExample>>,
+    mut next: ResMut<NextState<ActiveExample>>,
+) {
+    if keys.just_pressed(KeyCode::PageUp) {
+        next.set(neighbour(active.get(), -1));
+    } else if keys.just_pressed(KeyCode::PageDown) {
+        next.set(neighbour(active.get(), 1));
+    }
+}
+
+fn menu_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &MenuButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut list: Query<&mut Style, With<ExampleList>>,
+    active: Res<State<ActiveExample>>,
+    mut next: ResMut<NextState<ActiveExample>>,
+) {
+    for (interaction, menu_button, mut color) in
+        &mut interaction_query
+    {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                match *menu_button {
+                    MenuButton::Previous => next
+                        .set(neighbour(active.get(), -1)),
+                    MenuButton::Next => {
+                        next.set(neighbour(active.get(), 1))
+                    }
+                    MenuButton::ToggleList => {
+                        for mut style in list.iter_mut() {
+                            style.display =
+                                match style.display {
+                                    Display::None => {
+                                        Display::Flex
+                                    }
+                                    _ => Display::None,
+                                };
+                        }
+                    }
+                    MenuButton::Example(id) => {
+                        next.set(ActiveExample(id));
+                        for mut style in list.iter_mut() {
+                            style.display = Display::None;
+                        }
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+fn update_menu_title(
+    active: Res<State<ActiveExample>>,
+    mut title: Query<&mut Text, With<MenuTitle>>,
+) {
+    if !active.is_changed() {
+        return;
+    }
+    let name = registry::find(active.get().0)
+        .map(|example| example.title)
+        .unwrap_or("Examples");
+    for mut text in title.iter_mut() {
+        text.sections[0].value = name.to_string();
+    }
+}
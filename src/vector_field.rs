@@ -0,0 +1,204 @@
+//! Arrows on a grid showing which way, and how far, the
+//! closest surface is: the negated gradient of the distance
+//! outside a shape, the gradient inside, scaled by the
+//! distance.
+//!
+//! [`VectorField::arrows`] works with any [`Sdf`]. In the
+//! gradient examples V shows the arrows for the
+//! [`CurrentShape`].
+
+use bevy::prelude::*;
+
+use crate::{
+    center_sdf, gradient::CurrentShape, params::Params,
+    scene_to_world, sdf::Sdf, switcher::ActiveExample,
+};
+
+/// Where the arrows go and how long they are.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct VectorField {
+    pub enabled: bool,
+    /// How many arrows fit across the window's shorter
+    /// side.
+    pub cells: u32,
+    /// The length of an arrow over the distance at its
+    /// start. 1 ends every arrow on the surface, where the
+    /// distance is exact.
+    pub scale: f32,
+}
+
+impl Default for VectorField {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cells: 16,
+            scale: 0.25,
+        }
+    }
+}
+
+/// One arrow, in scene coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Arrow {
+    pub start: Vec2,
+    pub end: Vec2,
+    /// The distance at `start`.
+    pub distance: f32,
+}
+
+impl VectorField {
+    /// Reads the `vector_field` parameters the gradient
+    /// examples declare.
+    pub fn from_params(params: &Params) -> Self {
+        Self {
+            enabled: params.get("vector_field"),
+            cells: params.get("vector_field_cells"),
+            scale: params.get("vector_field_scale"),
+        }
+    }
+
+    /// The arrows for the grid cells between `min` and
+    /// `max`, one in the middle of each. Points where the
+    /// gradient vanishes, like the middle of a circle, get
+    /// none.
+    pub fn arrows(
+        &self,
+        sdf: &impl Sdf,
+        min: Vec2,
+        max: Vec2,
+    ) -> Vec<Arrow> {
+        let size = max - min;
+        let spacing =
+            size.min_element() / self.cells.max(1) as f32;
+        let count = (size / spacing).floor();
+        // centre the grid in the region
+        let first = min
+            + (size - count * spacing) / 2.
+            + spacing / 2.;
+        let mut arrows = vec![];
+        for y in 0..count.y as u32 {
+            for x in 0..count.x as u32 {
+                let start = first
+                    + Vec2::new(x as f32, y as f32)
+                        * spacing;
+                let distance = sdf.distance(start);
+                let towards_surface = -sdf
+                    .gradient(start)
+                    .normalize_or_zero()
+                    * distance.signum();
+                if towards_surface == Vec2::ZERO {
+                    continue;
+                }
+                arrows.push(Arrow {
+                    start,
+                    end: start
+                        + towards_surface
+                            * distance.abs()
+                            * self.scale,
+                    distance,
+                });
+            }
+        }
+        arrows
+    }
+
+    /// Draws the arrows for the whole window, the size of
+    /// `resolution`, in `color`.
+    pub fn draw(
+        &self,
+        gizmos: &mut Gizmos,
+        sdf: &impl Sdf,
+        resolution: Vec2,
+        color: Color,
+    ) {
+        let min = center_sdf(Vec2::ZERO, resolution);
+        let max = center_sdf(Vec2::ONE, resolution);
+        for arrow in self.arrows(sdf, min, max) {
+            draw_arrow(
+                gizmos,
+                scene_to_world(arrow.start, resolution),
+                scene_to_world(arrow.end, resolution),
+                color,
+            );
+        }
+    }
+}
+
+/// A line from `start` to `end` with a head at `end`.
+pub fn draw_arrow(
+    gizmos: &mut Gizmos,
+    start: Vec2,
+    end: Vec2,
+    color: Color,
+) {
+    gizmos.line_2d(start, end, color);
+    let back = start - end;
+    let head = back.clamp_length_max(6.) * 0.6;
+    for angle in [-0.5, 0.5] {
+        gizmos.line_2d(
+            end,
+            end + Vec2::from_angle(angle).rotate(head),
+            color,
+        );
+    }
+}
+
+/// Shows `field` while `example` runs. V turns it on and
+/// off.
+pub fn add_vector_field(
+    app: &mut App,
+    example: &'static str,
+    field: VectorField,
+) {
+    let example = ActiveExample(example);
+    app.add_systems(
+        OnEnter(example),
+        move |mut commands: Commands| {
+            commands.insert_resource(field)
+        },
+    )
+    .add_systems(
+        OnExit(example),
+        |mut commands: Commands| {
+            commands.remove_resource::<VectorField>()
+        },
+    )
+    .add_systems(
+        Update,
+        (toggle_vector_field, draw_vector_field)
+            .run_if(in_state(example)),
+    );
+}
+
+fn toggle_vector_field(
+    keys: Res<Input<KeyCode>>,
+    field: Option<ResMut<VectorField>>,
+) {
+    if let Some(mut field) = field {
+        if keys.just_pressed(KeyCode::V) {
+            field.enabled = !field.enabled;
+        }
+    }
+}
+
+fn draw_vector_field(
+    field: Option<Res<VectorField>>,
+    shape: Option<Res<CurrentShape>>,
+    windows: Query<&Window>,
+    mut gizmos: Gizmos,
+) {
+    let (Some(field), Some(shape), Ok(window)) =
+        (field, shape, windows.get_single())
+    else {
+        return;
+    };
+    if !field.enabled {
+        return;
+    }
+    field.draw(
+        &mut gizmos,
+        &shape.0,
+        Vec2::new(window.width(), window.height()),
+        Color::WHITE,
+    );
+}
@@ -0,0 +1,151 @@
+//! Which of several SDFs is closest where. A scene that is
+//! the `min` of its shapes' SDFs takes every distance from
+//! the closest shape, so these are the regions each shape
+//! owns in the union, and the bisectors between them.
+
+use bevy::{
+    prelude::*,
+    render::render_resource::{
+        Extent3d, TextureDimension, TextureFormat,
+    },
+};
+
+use crate::{marching_squares::MarchingSquares, sdf::Sdf};
+
+/// The index of the SDF in `sdfs` closest to `point`, or
+/// `None` if there are none.
+pub fn closest<S: Sdf>(
+    sdfs: &[S],
+    point: Vec2,
+) -> Option<usize> {
+    sdfs.iter()
+        .map(|sdf| sdf.distance(point))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+}
+
+/// The bisectors between `sdfs` from `min` to `max`, where
+/// the closest two are equally close, as polylines.
+///
+/// The bisector of two shapes is the zero contour of the
+/// difference of their SDFs, which `marching` traces. It's
+/// cut off where a third shape is closer than both.
+pub fn bisectors<S: Sdf>(
+    marching: &MarchingSquares,
+    sdfs: &[S],
+    min: Vec2,
+    max: Vec2,
+) -> Vec<Vec<Vec2>> {
+    let mut polylines = vec![];
+    for i in 0..sdfs.len() {
+        for j in i + 1..sdfs.len() {
+            let (a, b) = (&sdfs[i], &sdfs[j]);
+            let difference = |point: Vec2| {
+                a.distance(point) - b.distance(point)
+            };
+            // how much further the closest other shape is
+            // than `a`, which is as close as `b` on the
+            // bisector
+            let margin = |point: Vec2| {
+                let others = sdfs
+                    .iter()
+                    .enumerate()
+                    .filter(|(k, _)| *k != i && *k != j)
+                    .map(|(_, sdf)| sdf.distance(point))
+                    .fold(f32::INFINITY, f32::min);
+                others - a.distance(point)
+            };
+            for polyline in
+                marching.extract(&difference, 0., min, max)
+            {
+                polylines.extend(clip(&polyline, margin));
+            }
+        }
+    }
+    polylines
+}
+
+/// The parts of `polyline` where `margin` isn't negative,
+/// ending where it crosses 0.
+fn clip(
+    polyline: &[Vec2],
+    margin: impl Fn(Vec2) -> f32,
+) -> Vec<Vec<Vec2>> {
+    let values: Vec<f32> = polyline
+        .iter()
+        .map(|point| margin(*point))
+        .collect();
+    let mut parts = vec![];
+    let mut part = vec![];
+    for (k, (&point, &value)) in
+        polyline.iter().zip(&values).enumerate()
+    {
+        if k > 0 && (values[k - 1] < 0.) != (value < 0.) {
+            let before = values[k - 1];
+            part.push(
+                polyline[k - 1]
+                    .lerp(point, before / (before - value)),
+            );
+            if value < 0. {
+                parts.push(std::mem::take(&mut part));
+            }
+        }
+        if value >= 0. {
+            part.push(point);
+        }
+    }
+    parts.push(part);
+    parts.retain(|part| part.len() > 1);
+    parts
+}
+
+/// The regions of `sdfs` from `min` to `max`, `size`
+/// texels across: every texel in the colour in `colors` of
+/// the closest SDF, at half brightness outside it. Rows run
+/// from `max.y` down to `min.y`, as the world does on
+/// screen.
+pub fn region_image<S: Sdf>(
+    sdfs: &[S],
+    colors: &[Color],
+    min: Vec2,
+    max: Vec2,
+    size: UVec2,
+) -> Image {
+    let texel = (max - min) / size.as_vec2();
+    let mut data =
+        Vec::with_capacity((size.x * size.y * 4) as usize);
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let point = Vec2::new(
+                min.x + (x as f32 + 0.5) * texel.x,
+                max.y - (y as f32 + 0.5) * texel.y,
+            );
+            let Some(index) = closest(sdfs, point) else {
+                data.extend([0; 4]);
+                continue;
+            };
+            let shade = if sdfs[index].distance(point) > 0.
+            {
+                0.5
+            } else {
+                1.
+            };
+            let [r, g, b, _] = colors[index].as_rgba_f32();
+            data.extend(
+                [r * shade, g * shade, b * shade, 1.]
+                    .map(|channel| (channel * 255.) as u8),
+            );
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use yt_raymarch_2d::sdf::{
+    closest_point_by_gradient, Sdf, Shape,
+};
+
+/// A grid over the shapes, offset so no point lands
+/// exactly on a symmetry axis.
+fn points() -> impl Iterator<Item = Vec2> {
+    (-10..=10).flat_map(|y| {
+        (-10..=10).map(move |x| {
+            Vec2::new(x as f32, y as f32) * 0.1 + 0.003
+        })
+    })
+}
+
+#[test]
+fn closest_points_are_on_the_surface() {
+    for shape in Shape::ALL {
+        for point in points() {
+            let closest = shape.closest_point(point);
+            let distance = shape.distance(closest);
+            assert!(
+                distance.abs() < 0.001,
+                "{shape:?}: {point} -> {closest} is {distance} from the surface"
+            );
+        }
+    }
+}
+
+#[test]
+fn exact_distances_reach_the_closest_point() {
+    for shape in [
+        Shape::Circle,
+        Shape::Box,
+        Shape::EquilateralTriangle,
+    ] {
+        for point in points() {
+            let closest = shape.closest_point(point);
+            let error = point.distance(closest)
+                - shape.distance(point).abs();
+            assert!(
+                error.abs() < 0.0001,
+                "{shape:?}: {point} -> {closest}"
+            );
+        }
+    }
+}
+
+#[test]
+fn the_gradient_finds_the_analytic_point() {
+    // off the box's diagonals, where two sides are as
+    // close
+    for point in [
+        Vec2::new(0.9, 0.2),
+        Vec2::new(-0.1, 0.3),
+        Vec2::new(0.3, -0.7),
+    ] {
+        for shape in [Shape::Circle, Shape::Box] {
+            let analytic = shape.closest_point(point);
+            let gradient =
+                closest_point_by_gradient(&shape, point);
+            assert!(
+                analytic.distance(gradient) < 0.001,
+                "{shape:?}: {point} -> {analytic} vs {gradient}"
+            );
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! The CPU contours have to draw what `draw_contours` does.
+
+use bevy::prelude::*;
+use yt_raymarch_2d::contours::Contours;
+
+/// The distance one pixel covers.
+const WIDTH: f32 = 0.002;
+
+fn unfaded() -> Contours {
+    Contours {
+        enabled: true,
+        fade: 0.,
+        ..default()
+    }
+}
+
+#[test]
+fn no_fade_leaves_the_colour_between_lines() {
+    let color = Vec4::new(0.2, 0.6, 0.9, 1.);
+    for distance in [-0.25, -0.05, 0.05, 0.15, 0.35] {
+        assert_eq!(
+            unfaded().apply(color, distance, WIDTH),
+            color,
+            "at {distance}"
+        );
+    }
+}
+
+#[test]
+fn lines_are_drawn_at_multiples_of_the_spacing() {
+    let contours = unfaded();
+    let light = Vec4::new(0.8, 0.8, 0.8, 1.);
+    let dark = Vec4::new(0.05, 0.05, 0.05, 1.);
+    for line in [-3., 2., 5.] {
+        let distance = line * contours.spacing;
+        let on_light =
+            contours.apply(light, distance, WIDTH);
+        let on_dark = contours.apply(dark, distance, WIDTH);
+        assert!(
+            on_light.truncate().max_element() < 0.8,
+            "{on_light} at {distance}"
+        );
+        assert!(
+            on_dark.truncate().min_element() > 0.05,
+            "{on_dark} at {distance}"
+        );
+        assert_eq!(on_light.w, 1.);
+    }
+}
@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use image::Rgba;
+use yt_raymarch_2d::{
+    cpu_renderer::{ColorMode, CpuRenderer},
+    exactness::ExactnessCheck,
+    raymarch::{ColorBy, StepHeatmap},
+    sdf::Shape,
+};
+
+const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const GREEN: Rgba<u8> = Rgba([0, 255, 0, 255]);
+
+fn pixel(
+    renderer: CpuRenderer,
+    x: u32,
+    y: u32,
+) -> Rgba<u8> {
+    *renderer.render(&Shape::Circle).get_pixel(x, y)
+}
+
+#[test]
+fn gradient_keeps_the_aspect_of_wide_pictures() {
+    // 2:1, so x runs from -2 to 2 and y from -1 to 1
+    let renderer = || CpuRenderer::new(80, 40);
+
+    let Rgba([r, _, b, _]) = pixel(renderer(), 40, 20);
+    assert!(b > r, "the middle is inside");
+    // (0.825, 0.025): outside, though with uvs stretched
+    // over -1..1 it would be inside
+    let Rgba([r, _, b, _]) = pixel(renderer(), 56, 20);
+    assert!(r > b, "right of the circle is outside");
+}
+
+#[test]
+fn border_is_a_band_at_the_mouse_distance() {
+    // the mouse at (0.75, 0), 0.25 from the circle
+    let border = |thickness| {
+        CpuRenderer::new(64, 64).with_mode(
+            ColorMode::Border {
+                mouse: Vec2::new(0.875, 0.5),
+                thickness,
+            },
+        )
+    };
+
+    assert_eq!(pixel(border(0.05), 32, 32), BLACK);
+    assert_eq!(pixel(border(0.05), 1, 1), WHITE);
+    // (0.016, 0.766), 0.266 from the circle
+    assert_eq!(pixel(border(0.05), 32, 56), GREEN);
+    assert_eq!(pixel(border(0.005), 32, 56), WHITE);
+}
+
+#[test]
+fn circle_around_the_mouse_with_lights_on_and_off() {
+    // 2:1 again, the mouse at (1, 0), 0.5 from the circle
+    let circle = |lights| {
+        CpuRenderer::new(96, 48).with_mode(
+            ColorMode::Circle {
+                mouse: Vec2::new(0.75, 0.5),
+                lights,
+            },
+        )
+    };
+
+    assert_eq!(pixel(circle(true), 48, 24), WHITE);
+    assert_eq!(pixel(circle(false), 48, 24), BLACK);
+    assert_eq!(pixel(circle(true), 1, 1), BLACK);
+    // (1.354, 0.354), 0.5 from the mouse
+    assert_eq!(pixel(circle(true), 80, 32), GREEN);
+    assert_eq!(pixel(circle(false), 80, 32), GREEN);
+}
+
+#[test]
+fn steps_show_hits_and_escapes() {
+    let steps = CpuRenderer::new(64, 64).with_mode(
+        ColorMode::Steps(StepHeatmap {
+            origin: Vec2::new(0.75, 0.),
+            color_by: ColorBy::Termination,
+            ..default()
+        }),
+    );
+    let image = steps.render(&Shape::Circle);
+
+    // towards the circle
+    let Rgba([r, g, b, _]) = *image.get_pixel(32, 32);
+    assert!(g > r && g > b, "hits are green");
+    // away from it
+    let Rgba([r, g, b, _]) = *image.get_pixel(63, 32);
+    assert!(b > r && b > g, "escaped rays are blue");
+}
+
+#[test]
+fn exactness_is_grey_for_an_exact_sdf() {
+    let exactness = CpuRenderer::new(64, 64).with_mode(
+        ColorMode::Exactness(ExactnessCheck::default()),
+    );
+
+    let Rgba([r, g, b, _]) = pixel(exactness, 4, 4);
+    assert_eq!((r, g), (g, b));
+}
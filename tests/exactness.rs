@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use yt_raymarch_2d::exactness::{
+    primitives, Exactness, ExactnessCheck,
+};
+
+#[test]
+fn primitives_are_classified() {
+    let check = ExactnessCheck::default();
+    let expected = [
+        ("circle", Exactness::Exact),
+        ("box", Exactness::Exact),
+        ("triangle", Exactness::Exact),
+        // the inside of the middle, where the arms cross
+        ("x", Exactness::Bound),
+        ("cool-s", Exactness::Exact),
+        ("union", Exactness::Bound),
+        ("smooth union", Exactness::Bound),
+        ("scaled box", Exactness::NotLipschitz),
+        ("scaled box, rescaled distance", Exactness::Exact),
+    ];
+    let primitives = primitives();
+    assert_eq!(primitives.len(), expected.len());
+    for (name, exactness) in expected {
+        let (_, sdf) = primitives
+            .iter()
+            .find(|(primitive, _)| *primitive == name)
+            .expect("every expected primitive exists");
+        // an odd count puts a row and a column of samples
+        // on the axes, where the cool S has its creases
+        for samples in [127, 128] {
+            let check = ExactnessCheck { samples, ..check };
+            let report = check.check(
+                sdf.as_ref(),
+                Vec2::splat(-1.),
+                Vec2::splat(1.),
+            );
+            assert_eq!(
+                report.exactness, exactness,
+                "{name} with {samples} samples: {report:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Runs the example plugins without a window or a GPU, the
+//! way another app or a test would embed them.
+
+use bevy::{
+    prelude::*, reflect::Struct, sprite::Material2d,
+};
+use yt_raymarch_2d::{
+    apps::{
+        compare_shapes::{
+            self, CompareShapesConfig, CompareShapesPlugin,
+        },
+        single_sdf_distance_as_border as border,
+        single_sdf_distance_as_gradient as gradient,
+        single_sdf_distance_as_gradient_with_algorithm::{
+            self as algorithm,
+            SdfGradientWithAlgorithmPlugin,
+        },
+    },
+    bevy_plugin_shader2d::{
+        CompareLayout, Comparison, SdfHeadlessPlugin,
+        SdfMouse,
+    },
+    color_map::ColorMap,
+    contours::Contours,
+    gradient::{CurrentShape, GradientConfig},
+    material_params::MaterialPanel,
+    params::Params,
+    registry::EXAMPLES,
+    sdf::Shape,
+    switcher::{ActiveExample, ExampleEntity},
+};
+
+fn example_entities(app: &mut App) -> usize {
+    app.world
+        .query_filtered::<Entity, With<ExampleEntity>>()
+        .iter(&app.world)
+        .count()
+}
+
+fn switch_to(app: &mut App, id: &'static str) {
+    app.world
+        .resource_mut::<NextState<ActiveExample>>()
+        .set(ActiveExample(id));
+    app.update();
+}
+
+fn algorithm_materials(app: &App) -> usize {
+    app.world
+        .resource::<Assets<algorithm::SdfDemoMaterial>>()
+        .len()
+}
+
+#[test]
+fn every_example_starts_and_stops() {
+    let mut app = App::new();
+    app.add_plugins(SdfHeadlessPlugin);
+    for example in EXAMPLES {
+        (example.add)(&mut app, &Params::defaults(example));
+    }
+    app.update();
+    assert_eq!(example_entities(&mut app), 0);
+
+    for example in EXAMPLES {
+        switch_to(&mut app, example.id);
+        assert_eq!(
+            app.world
+                .resource::<State<ActiveExample>>()
+                .get(),
+            &ActiveExample(example.id)
+        );
+        assert!(
+            example_entities(&mut app) > 0,
+            "{} spawned nothing",
+            example.id
+        );
+    }
+
+    switch_to(&mut app, "");
+    assert_eq!(example_entities(&mut app), 0);
+    assert!(!app.world.contains_resource::<CurrentShape>());
+}
+
+#[test]
+fn one_example_embedded_with_a_config() {
+    let mut app = App::new();
+    app.add_plugins((
+        SdfHeadlessPlugin,
+        SdfGradientWithAlgorithmPlugin {
+            config: GradientConfig {
+                shape: Some(Shape::CoolS),
+                ..default()
+            },
+        },
+    ));
+    switch_to(&mut app, algorithm::ID);
+
+    assert_eq!(
+        app.world.resource::<CurrentShape>().0,
+        Shape::CoolS
+    );
+    assert_eq!(algorithm_materials(&app), 1);
+
+    switch_to(&mut app, "");
+    assert!(!app.world.contains_resource::<CurrentShape>());
+    assert_eq!(algorithm_materials(&app), 0);
+}
+
+/// The field `name` of every `M` material.
+fn material_fields<M, T>(app: &App, name: &str) -> Vec<T>
+where
+    M: Material2d + Struct,
+    T: Reflect + Copy,
+{
+    app.world
+        .resource::<Assets<M>>()
+        .iter()
+        .map(|(_, material)| {
+            *material
+                .field(name)
+                .and_then(|field| field.downcast_ref::<T>())
+                .unwrap()
+        })
+        .collect()
+}
+
+fn border_thickness(app: &App) -> Vec<f32> {
+    material_fields::<border::SdfDemoMaterial, _>(
+        app,
+        "thickness",
+    )
+}
+
+#[test]
+fn material_fields_follow_params_and_the_panel() {
+    let params = Params::parse_query(
+        "?example=single-sdf-distance-as-border&thickness=0.02",
+    )
+    .unwrap();
+    let example = params.example().unwrap();
+    let params = params.validate(example).unwrap();
+
+    let mut app = App::new();
+    app.add_plugins(SdfHeadlessPlugin);
+    (example.add)(&mut app, &params);
+    switch_to(&mut app, border::ID);
+    assert_eq!(border_thickness(&app), [0.02]);
+
+    app.world
+        .resource_mut::<MaterialPanel>()
+        .params
+        .insert(
+            "thickness".to_string(),
+            Some("0.04".into()),
+        );
+    app.update();
+    assert_eq!(border_thickness(&app), [0.04]);
+
+    // the tweak is kept for the next time the example starts
+    switch_to(&mut app, "");
+    switch_to(&mut app, border::ID);
+    assert_eq!(border_thickness(&app), [0.04]);
+}
+
+#[test]
+fn color_maps_are_material_params() {
+    let params = Params::parse_query(
+        "?example=single-sdf-distance-as-gradient&color_map=cividis&log_scale",
+    )
+    .unwrap();
+    let example = params.example().unwrap();
+    let params = params.validate(example).unwrap();
+
+    let mut app = App::new();
+    app.add_plugins(SdfHeadlessPlugin);
+    (example.add)(&mut app, &params);
+    switch_to(&mut app, gradient::ID);
+    let field = |name| {
+        material_fields::<gradient::SdfDemoMaterial, u32>(
+            &app, name,
+        )
+    };
+    assert_eq!(
+        field("color_map"),
+        [ColorMap::Cividis.as_u32()]
+    );
+    assert_eq!(field("log_scale"), [1]);
+}
+
+fn border_contours(app: &App) -> Vec<Vec4> {
+    material_fields::<border::SdfDemoMaterial, _>(
+        app, "contours",
+    )
+}
+
+#[test]
+fn contours_reach_the_material() {
+    let mut app = App::new();
+    app.add_plugins((
+        SdfHeadlessPlugin,
+        border::SdfBorderPlugin,
+    ));
+    switch_to(&mut app, border::ID);
+    assert_eq!(border_contours(&app), [Vec4::ZERO]);
+
+    app.world.resource_mut::<Contours>().enabled = true;
+    app.update();
+    let uniform = Contours {
+        enabled: true,
+        ..default()
+    }
+    .uniform();
+    assert_eq!(border_contours(&app), [uniform]);
+
+    // a new start of the example picks them up too
+    switch_to(&mut app, "");
+    switch_to(&mut app, border::ID);
+    assert_eq!(border_contours(&app), [uniform]);
+}
+
+#[test]
+fn both_sides_of_a_comparison_follow_the_mouse() {
+    let mut app = App::new();
+    app.add_plugins((
+        SdfHeadlessPlugin,
+        CompareShapesPlugin {
+            config: CompareShapesConfig {
+                layout: CompareLayout::Wipe,
+                ..default()
+            },
+        },
+    ));
+    switch_to(&mut app, compare_shapes::ID);
+    assert_eq!(
+        app.world.resource::<Comparison>().layout,
+        CompareLayout::Wipe
+    );
+
+    app.world.resource_mut::<SdfMouse>().position =
+        Vec2::new(0.25, 0.75);
+    app.update();
+    let mice: Vec<Vec2> = material_fields::<
+        gradient::SdfDemoMaterial,
+        _,
+    >(&app, "mouse");
+    assert_eq!(mice, [Vec2::new(0.25, 0.75); 2]);
+
+    switch_to(&mut app, "");
+    assert!(!app.world.contains_resource::<Comparison>());
+}
@@ -0,0 +1,45 @@
+//! Links to a view have to reopen the same view.
+
+use yt_raymarch_2d::{params::Params, registry::EXAMPLES};
+
+fn validated(query: &str) -> Params {
+    let params = Params::parse_query(query).unwrap();
+    let example = params.example().unwrap();
+    params.validate(example).unwrap()
+}
+
+#[test]
+fn links_round_trip() {
+    let params = validated(
+        "?example=playground&shader=my%20shader.wgsl&mouse=0.25,0.75&zoom=2.5&center=-0.5,0.1",
+    );
+    let link = params.to_query("playground");
+    assert_eq!(
+        link,
+        "?example=playground&shader=my%20shader.wgsl&mouse=0.25,0.75&zoom=2.5&center=-0.5,0.1"
+    );
+    let reopened = validated(&link);
+    for name in ["shader", "mouse", "zoom", "center"] {
+        assert_eq!(
+            reopened.value(name),
+            params.value(name)
+        );
+    }
+}
+
+#[test]
+fn defaults_are_left_out() {
+    for example in EXAMPLES {
+        assert_eq!(
+            Params::defaults(example).to_query(example.id),
+            format!("?example={}", example.id)
+        );
+    }
+    let params = validated(
+        "?example=single-sdf-distance-as-border&thickness=0.005&zoom=1",
+    );
+    assert_eq!(
+        params.to_query("single-sdf-distance-as-border"),
+        "?example=single-sdf-distance-as-border"
+    );
+}
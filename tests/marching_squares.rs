@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use yt_raymarch_2d::{
+    marching_squares::{MarchingSquares, Outline},
+    sdf::{sd_box, Sdf, Shape},
+};
+
+#[test]
+fn the_circle_is_one_closed_loop_on_its_surface() {
+    let polylines = MarchingSquares::default().extract(
+        &Shape::Circle,
+        0.,
+        Vec2::splat(-1.),
+        Vec2::splat(1.),
+    );
+
+    assert_eq!(polylines.len(), 1);
+    let polyline = &polylines[0];
+    assert_eq!(polyline.first(), polyline.last());
+    for point in polyline {
+        assert!(
+            Shape::Circle.distance(*point).abs() < 1e-3
+        );
+    }
+}
+
+#[test]
+fn other_iso_values_trace_offset_lines() {
+    let square = |p: Vec2| sd_box(p, Vec2::splat(0.3));
+    for iso in [-0.1, 0.2] {
+        let polylines = MarchingSquares::default().extract(
+            &square,
+            iso,
+            Vec2::splat(-1.),
+            Vec2::splat(1.),
+        );
+
+        assert_eq!(polylines.len(), 1, "at {iso}");
+        for point in polylines.iter().flatten() {
+            assert!(
+                (square.distance(*point) - iso).abs()
+                    < 1e-2,
+                "{point} at {iso}"
+            );
+        }
+    }
+}
+
+#[test]
+fn separate_shapes_and_clipped_ones_give_separate_lines() {
+    let left = Vec2::new(-0.5, 0.);
+    let right = Vec2::new(0.9, 0.);
+    let circles = |p: Vec2| {
+        ((p - left).length() - 0.3)
+            .min((p - right).length() - 0.3)
+    };
+    let polylines = MarchingSquares::default().extract(
+        &circles,
+        0.,
+        Vec2::splat(-1.),
+        Vec2::splat(1.),
+    );
+
+    assert_eq!(polylines.len(), 2);
+    let closed = polylines
+        .iter()
+        .filter(|polyline| {
+            polyline.first() == polyline.last()
+        })
+        .count();
+    // the right circle runs off the edge of the region
+    assert_eq!(closed, 1);
+}
+
+#[test]
+fn outlines_are_traced_again_only_for_a_new_region() {
+    let marching = MarchingSquares::default();
+    let small = |p: Vec2| sd_box(p, Vec2::splat(0.2));
+    let (min, max) = (Vec2::splat(-1.), Vec2::splat(1.));
+    let mut outline = Outline::default();
+    let traced =
+        outline.trace(&marching, &Shape::Circle, min, max);
+    assert_eq!(
+        traced,
+        marching.extract(&Shape::Circle, 0., min, max)
+    );
+
+    // the same region keeps the lines already traced
+    let kept = outline.trace(&marching, &small, min, max);
+    assert_eq!(
+        kept,
+        marching.extract(&Shape::Circle, 0., min, max)
+    );
+
+    let max = Vec2::splat(0.5);
+    let retraced =
+        outline.trace(&marching, &small, min, max);
+    assert_eq!(
+        retraced,
+        marching.extract(&small, 0., min, max)
+    );
+}
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use yt_raymarch_2d::{
+    medial_axis::MedialAxis,
+    sdf::{Sdf, Shape},
+};
+
+#[test]
+fn the_box_skeleton_is_its_diagonals() {
+    let medial_axis = MedialAxis::default();
+    let polylines = medial_axis.extract(
+        &Shape::Box,
+        Vec2::splat(-1.),
+        Vec2::splat(1.),
+    );
+    let spacing = 2. / medial_axis.cells as f32;
+    let points: Vec<_> =
+        polylines.iter().flatten().collect();
+    assert!(!points.is_empty());
+    for point in &points {
+        let p = point.position;
+        assert!(
+            (p.x.abs() - p.y.abs()).abs() < spacing,
+            "{point:?} is off the diagonals"
+        );
+        assert!(
+            (point.radius + Shape::Box.distance(p)).abs()
+                < 0.0001,
+            "{point:?}"
+        );
+    }
+    // every arm reaches from the middle towards its corner
+    for corner in [
+        Vec2::new(1., 1.),
+        Vec2::new(-1., 1.),
+        Vec2::new(1., -1.),
+        Vec2::new(-1., -1.),
+    ] {
+        let reach = points
+            .iter()
+            .map(|point| point.position.dot(corner))
+            .fold(f32::MIN, f32::max);
+        assert!(reach > 0.9, "{corner}: {reach}");
+    }
+}
+
+#[test]
+fn the_circle_skeleton_is_its_centre() {
+    let polylines = MedialAxis::default().extract(
+        &Shape::Circle,
+        Vec2::splat(-1.),
+        Vec2::splat(1.),
+    );
+    assert!(!polylines.is_empty());
+    for point in polylines.iter().flatten() {
+        assert!(
+            point.position.length() < 0.05,
+            "{point:?}"
+        );
+        assert!((point.radius - 0.5).abs() < 0.05);
+    }
+}
@@ -0,0 +1,162 @@
+use bevy::{
+    prelude::*,
+    render::mesh::{Indices, VertexAttributeValues},
+};
+use std::f32::consts::PI;
+use yt_raymarch_2d::{
+    mesher::SdfMesher,
+    sdf::{Sdf, Shape},
+};
+
+#[test]
+fn the_circle_mesh_covers_the_circle() {
+    let triangulation = SdfMesher::default().triangulate(
+        &Shape::Circle,
+        Vec2::splat(-1.),
+        Vec2::splat(1.),
+    );
+
+    let area = PI * 0.5 * 0.5;
+    assert_eq!(triangulation.dropped, 0);
+    assert!(
+        (triangulation.area() - area).abs() < 0.01,
+        "{}",
+        triangulation.area()
+    );
+}
+
+#[test]
+fn holes_are_left_out() {
+    let ring = |p: Vec2| (p.length() - 0.6).abs() - 0.2;
+    let triangulation = SdfMesher::default().triangulate(
+        &ring,
+        Vec2::splat(-1.),
+        Vec2::splat(1.),
+    );
+
+    let area = PI * (0.8 * 0.8 - 0.4 * 0.4);
+    assert_eq!(triangulation.dropped, 0);
+    assert!(
+        (triangulation.area() - area).abs() < 0.01,
+        "{}",
+        triangulation.area()
+    );
+    for triangle in triangulation.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|corner| {
+            triangulation.positions
+                [triangle[corner] as usize]
+        });
+        assert!((b - a).perp_dot(c - a) > 0.);
+        assert!(ring.distance((a + b + c) / 3.) < 0.01);
+    }
+}
+
+#[test]
+fn the_mesh_is_cut_off_at_the_bounds_and_uvs_span_them() {
+    let min = Vec2::ZERO;
+    let max = Vec2::ONE;
+    let circle = |p: Vec2| p.length() - 0.8;
+    let mesher = SdfMesher {
+        cells: 64,
+        tolerance: 0.01,
+    };
+    let triangulation =
+        mesher.triangulate(&circle, min, max);
+    assert!(
+        (triangulation.area() - PI * 0.64 / 4.).abs()
+            < 0.01,
+        "{}",
+        triangulation.area()
+    );
+
+    let mesh = mesher.mesh(&circle, min, max);
+    let Some(VertexAttributeValues::Float32x2(uvs)) =
+        mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+    else {
+        panic!("the mesh has no uvs");
+    };
+    assert_eq!(uvs.len(), triangulation.positions.len());
+    for uv in uvs {
+        assert!(uv
+            .iter()
+            .all(|t| (-1e-4..=1. + 1e-4).contains(t)));
+    }
+    let Some(Indices::U32(indices)) = mesh.indices() else {
+        panic!("the mesh has no indices");
+    };
+    assert_eq!(*indices, triangulation.indices);
+}
+
+/// The area inside `sdf` between `min` and `max`, from the
+/// centres of a fine grid's cells.
+fn sampled_area(
+    sdf: &impl Sdf,
+    min: Vec2,
+    max: Vec2,
+) -> f32 {
+    let cells = 500;
+    let cell = (max - min) / cells as f32;
+    let inside = (0..cells * cells)
+        .filter(|index| {
+            let (x, y) = (index % cells, index / cells);
+            let point = min
+                + (Vec2::new(x as f32, y as f32) + 0.5)
+                    * cell;
+            sdf.distance(point) < 0.
+        })
+        .count();
+    inside as f32 * cell.x * cell.y
+}
+
+/// Every triangle `mesher` makes of `sdf` is
+/// counter-clockwise, none were left out, and together
+/// they cover its inside once.
+fn check_triangulation(mesher: SdfMesher, sdf: &impl Sdf) {
+    let (min, max) = (Vec2::splat(-1.), Vec2::splat(1.));
+    let triangulation = mesher.triangulate(sdf, min, max);
+    for triangle in triangulation.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|corner| {
+            triangulation.positions
+                [triangle[corner] as usize]
+        });
+        assert!((b - a).perp_dot(c - a) > 0.);
+    }
+    assert_eq!(triangulation.dropped, 0);
+    let area = sampled_area(sdf, min, max);
+    assert!(
+        (triangulation.area() - area).abs() < 0.01,
+        "{} instead of {area}",
+        triangulation.area()
+    );
+}
+
+#[test]
+fn concave_outlines_are_covered_once() {
+    check_triangulation(
+        SdfMesher::default(),
+        &Shape::CoolS,
+    );
+    check_triangulation(SdfMesher::default(), &Shape::X);
+}
+
+#[test]
+fn holes_in_a_row_are_bridged_around_each_other() {
+    let holes = |p: Vec2| {
+        let left = p.distance(Vec2::new(-0.6, 0.)) - 0.12;
+        let right = p.distance(Vec2::new(0.6, 0.)) - 0.12;
+        left.min(right)
+    };
+    let slab = |p: Vec2| {
+        let d = p.abs() - Vec2::new(0.85, 0.4);
+        d.max(Vec2::ZERO).length() + d.max_element().min(0.)
+    };
+    // the left hole's closest point on the outline is
+    // straight through it, past one of its own points
+    let mesher = SdfMesher {
+        cells: 48,
+        tolerance: 0.,
+    };
+    check_triangulation(mesher, &|p: Vec2| {
+        slab(p).max(-holes(p))
+    });
+}
@@ -0,0 +1,132 @@
+//! Bad parameters are reported, not panicked on.
+
+use yt_raymarch_2d::{
+    bevy_plugin_shader2d::MAX_ZOOM,
+    params::{ParamError, Params},
+};
+
+fn errors(query: &str) -> Vec<ParamError> {
+    let params = Params::parse_query(query).unwrap();
+    let example = params.example().unwrap();
+    params.validate(example).unwrap_err()
+}
+
+#[test]
+fn flags_without_values_are_parsed() {
+    let params =
+        Params::parse_query("?example=foo&flag").unwrap();
+    assert!(params.contains("flag"));
+    assert_eq!(
+        params.example().err(),
+        Some(ParamError::UnknownExample("foo".to_string()))
+    );
+}
+
+#[test]
+fn a_flag_where_a_value_is_needed_is_missing_one() {
+    assert_eq!(
+        errors(
+            "?example=single-sdf-distance-as-circle&lights"
+        ),
+        [ParamError::MissingValue("lights".to_string())]
+    );
+}
+
+#[test]
+fn unknown_enum_values_are_invalid() {
+    assert_eq!(
+        errors(
+            "?example=single-sdf-distance-as-circle&lights=dim"
+        ),
+        [ParamError::Invalid {
+            name: "lights".to_string(),
+            value: "dim".to_string(),
+            expected: "one of off, on".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn bad_escapes_are_rejected() {
+    for query in [
+        "?example=playground&shader=a%2",
+        "?example=playground&shader=a%",
+        "?example=playground&shader=a%zz",
+        "?example=playground&shader=a%+F",
+        "?example=playground&shader=a%-1",
+        // not UTF-8
+        "?example=playground&shader=a%FF",
+    ] {
+        assert!(
+            matches!(
+                Params::parse_query(query),
+                Err(ParamError::BadEncoding(_))
+            ),
+            "{query}"
+        );
+    }
+}
+
+#[test]
+fn unknown_examples_are_reported() {
+    let params =
+        Params::parse_query("?example=nope").unwrap();
+    assert_eq!(
+        params.example().err(),
+        Some(ParamError::UnknownExample(
+            "nope".to_string()
+        ))
+    );
+    let params = Params::parse_query("?zoom=2").unwrap();
+    assert_eq!(
+        params.example().err(),
+        Some(ParamError::NoExample)
+    );
+}
+
+#[test]
+fn unknown_params_are_reported() {
+    assert_eq!(
+        errors(
+            "?example=single-sdf-distance-as-circle&size=3"
+        ),
+        [ParamError::UnknownParam {
+            example: "single-sdf-distance-as-circle",
+            name: "size".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn numbers_out_of_range_are_invalid() {
+    assert_eq!(
+        errors("?example=playground&zoom=0.5"),
+        [ParamError::Invalid {
+            name: "zoom".to_string(),
+            value: "0.5".to_string(),
+            expected: format!(
+                "a number from 1 to {MAX_ZOOM}"
+            ),
+        }]
+    );
+}
+
+#[test]
+fn whole_numbers_are_not_truncated() {
+    assert_eq!(
+        errors("?example=playground&contour_major=2.5"),
+        [ParamError::Invalid {
+            name: "contour_major".to_string(),
+            value: "2.5".to_string(),
+            expected: "a whole number from 0 to 20"
+                .to_string(),
+        }]
+    );
+    let params = Params::parse_query(
+        "?example=playground&contour_major=3",
+    )
+    .unwrap();
+    let example = params.example().unwrap();
+    let params = params.validate(example).unwrap();
+    assert_eq!(params.get::<u32>("contour_major"), 3);
+}
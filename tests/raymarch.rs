@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use yt_raymarch_2d::{
+    raymarch::{RayMarcher, Strategy, Termination},
+    sdf::Shape,
+};
+
+fn marcher(strategy: Strategy) -> RayMarcher {
+    RayMarcher {
+        strategy,
+        ..default()
+    }
+}
+
+#[test]
+fn both_strategies_hit_a_circle_head_on() {
+    for strategy in [Strategy::Sphere, Strategy::Relaxed] {
+        let march = marcher(strategy).march(
+            &Shape::Circle,
+            Vec2::new(-2., 0.),
+            Vec2::X,
+        );
+        assert_eq!(
+            march.termination,
+            Termination::Hit,
+            "{strategy:?}"
+        );
+        assert!(
+            (march.distance - 1.5).abs() < 0.001,
+            "{strategy:?} stopped at {march:?}"
+        );
+    }
+}
+
+#[test]
+fn grazing_rays_run_out_of_steps() {
+    let marcher = marcher(Strategy::Sphere);
+    let head_on = marcher.march(
+        &Shape::Box,
+        Vec2::new(-2., 0.),
+        Vec2::X,
+    );
+    let away = marcher.march(
+        &Shape::Box,
+        Vec2::new(-2., 0.),
+        -Vec2::X,
+    );
+    assert_eq!(head_on.termination, Termination::Hit);
+    assert!(head_on.steps <= 3, "{head_on:?}");
+    assert_eq!(away.termination, Termination::Escaped);
+    assert!(away.steps <= 3, "{away:?}");
+
+    // passes just outside the top of the box, where every
+    // step is only as long as the gap
+    let origin = Vec2::new(-2., -0.51);
+    let grazing =
+        marcher.march(&Shape::Box, origin, Vec2::X);
+    assert_eq!(
+        grazing.termination,
+        Termination::OutOfSteps
+    );
+    assert_eq!(grazing.steps, marcher.max_steps);
+
+    let patient = RayMarcher {
+        max_steps: 1000,
+        ..marcher
+    }
+    .march(&Shape::Box, origin, Vec2::X);
+    assert_eq!(patient.termination, Termination::Escaped);
+    assert!(patient.steps > 100, "{patient:?}");
+}
@@ -0,0 +1,22 @@
+//! The README's list of live demos has to be the one
+//! `update-readme` generates from the registry.
+
+use yt_raymarch_2d::registry;
+
+#[test]
+fn the_readme_lists_every_example() {
+    let readme = include_str!("../README.md");
+    let (Some(start), Some(end)) = (
+        readme.find("<!-- examples:start -->\n"),
+        readme.find("\n<!-- examples:end -->"),
+    ) else {
+        panic!("README.md is missing the examples markers");
+    };
+    let list = &readme
+        [start + "<!-- examples:start -->\n".len()..end];
+    assert_eq!(
+        list,
+        registry::readme_list(),
+        "run update-readme"
+    );
+}
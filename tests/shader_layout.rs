@@ -0,0 +1,448 @@
+//! Validates every shader in `assets/` with naga (no GPU
+//! required) and checks that the `@group(1) @binding(0)`
+//! uniform struct of each example shader lines up with the
+//! `#[uniform(0)]` fields of its Rust material, and that
+//! the colour maps in `sdf.wgsl` have the stops of
+//! [`ColorMap`].
+
+use std::path::{Path, PathBuf};
+
+use bevy::{
+    prelude::*,
+    reflect::{TypeInfo, Typed},
+    render::render_resource::ShaderRef,
+    sprite::Material2d,
+};
+use naga::{
+    valid::{Capabilities, ValidationFlags, Validator},
+    AddressSpace, ConstantInner, Expression, Module,
+    ScalarKind, ScalarValue, TypeInner, VectorSize,
+};
+use naga_oil::compose::{
+    ComposableModuleDescriptor, Composer,
+    NagaModuleDescriptor,
+};
+use yt_raymarch_2d::{apps::*, color_map::ColorMap};
+
+// Stand-ins for the bevy_sprite import modules. Only the
+// items the example shaders actually use are declared.
+const MESH2D_VIEW_BINDINGS: &str = r#"
+#define_import_path bevy_sprite::mesh2d_view_bindings
+
+struct View {
+    viewport: vec4<f32>,
+};
+
+struct Globals {
+    time: f32,
+    delta_time: f32,
+    frame_count: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> view: View;
+@group(0) @binding(1)
+var<uniform> globals: Globals;
+"#;
+
+const MESH2D_VERTEX_OUTPUT: &str = r#"
+#define_import_path bevy_sprite::mesh2d_vertex_output
+
+struct MeshVertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+"#;
+
+fn manifest_path(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(path)
+}
+
+fn composer() -> Composer {
+    let sdf_library = std::fs::read_to_string(
+        manifest_path("src/sdf.wgsl"),
+    )
+    .expect("src/sdf.wgsl should be readable");
+
+    let mut composer = Composer::default();
+    for (source, file_path) in [
+        (MESH2D_VIEW_BINDINGS, "mesh2d_view_bindings.wgsl"),
+        (MESH2D_VERTEX_OUTPUT, "mesh2d_vertex_output.wgsl"),
+        (sdf_library.as_str(), "src/sdf.wgsl"),
+    ] {
+        if let Err(err) = composer.add_composable_module(
+            ComposableModuleDescriptor {
+                source,
+                file_path,
+                ..default()
+            },
+        ) {
+            panic!("{}", err.emit_to_string(&composer));
+        }
+    }
+    composer
+}
+
+/// Composes and validates a shader from `assets/`,
+/// panicking with naga's report if either step fails.
+fn load_shader(asset: &Path) -> Module {
+    let file_path = manifest_path("assets").join(asset);
+    let source = std::fs::read_to_string(&file_path)
+        .unwrap_or_else(|err| {
+            panic!("failed to read {file_path:?}: {err}")
+        });
+    let file_path = file_path.to_string_lossy();
+
+    let mut composer = composer();
+    let module = match composer.make_naga_module(
+        NagaModuleDescriptor {
+            source: &source,
+            file_path: &file_path,
+            ..default()
+        },
+    ) {
+        Ok(module) => module,
+        Err(err) => {
+            panic!("{}", err.emit_to_string(&composer))
+        }
+    };
+
+    if let Err(err) = Validator::new(
+        ValidationFlags::all(),
+        Capabilities::default(),
+    )
+    .validate(&module)
+    {
+        panic!("{file_path} failed validation: {err:?}");
+    }
+    module
+}
+
+#[derive(Debug, PartialEq)]
+struct Member {
+    name: String,
+    ty: String,
+    offset: u32,
+}
+
+fn round_up(align: u32, offset: u32) -> u32 {
+    (offset + align - 1) / align * align
+}
+
+/// Lays out the reflected fields of a material the way
+/// `AsBindGroup` packs `#[uniform(0)]` fields: one struct,
+/// in declaration order, using WGSL's alignment rules.
+///
+/// Reflection can't see the `AsBindGroup` attributes, so
+/// every reflected scalar or vector field is taken to be
+/// part of uniform 0. Texture and sampler handles are
+/// skipped, but a plain-data field bound elsewhere would
+/// be laid out too, and a `#[reflect(ignore)]` uniform
+/// field would be missed.
+fn rust_members<M: Typed>() -> Vec<Member> {
+    let TypeInfo::Struct(info) = M::type_info() else {
+        panic!("materials are expected to be structs");
+    };
+
+    let mut offset = 0;
+    info.iter()
+        .filter(|field| {
+            !field.is::<Handle<Image>>()
+                && !field.is::<Option<Handle<Image>>>()
+        })
+        .map(|field| {
+            let (ty, align, size) = if field.is::<Color>()
+                || field.is::<Vec4>()
+            {
+                ("vec4<f32>", 16, 16)
+            } else if field.is::<Vec3>() {
+                ("vec3<f32>", 16, 12)
+            } else if field.is::<Vec2>() {
+                ("vec2<f32>", 8, 8)
+            } else if field.is::<f32>() {
+                ("f32", 4, 4)
+            } else if field.is::<u32>() {
+                ("u32", 4, 4)
+            } else if field.is::<i32>() {
+                ("i32", 4, 4)
+            } else {
+                panic!(
+                    "no WGSL equivalent known for {}: {}",
+                    field.name(),
+                    field.type_name()
+                );
+            };
+            offset = round_up(align, offset);
+            let member = Member {
+                name: field.name().to_string(),
+                ty: ty.to_string(),
+                offset,
+            };
+            offset += size;
+            member
+        })
+        .collect()
+}
+
+fn wgsl_type(inner: &TypeInner) -> String {
+    let scalar = |kind: &ScalarKind| match kind {
+        ScalarKind::Float => "f32",
+        ScalarKind::Uint => "u32",
+        ScalarKind::Sint => "i32",
+        ScalarKind::Bool => "bool",
+    };
+    match inner {
+        TypeInner::Scalar { kind, .. } => {
+            scalar(kind).to_string()
+        }
+        TypeInner::Vector { size, kind, .. } => {
+            let size = match size {
+                VectorSize::Bi => 2,
+                VectorSize::Tri => 3,
+                VectorSize::Quad => 4,
+            };
+            format!("vec{size}<{}>", scalar(kind))
+        }
+        other => format!("{other:?}"),
+    }
+}
+
+/// The members of the struct bound at
+/// `@group(1) @binding(0) var<uniform>`.
+fn shader_members(module: &Module) -> Vec<Member> {
+    let (_, global) = module
+        .global_variables
+        .iter()
+        .find(|(_, global)| {
+            global.space == AddressSpace::Uniform
+                && global.binding.as_ref().is_some_and(
+                    |binding| {
+                        binding.group == 1
+                            && binding.binding == 0
+                    },
+                )
+        })
+        .expect("no uniform at @group(1) @binding(0)");
+
+    let TypeInner::Struct { members, .. } =
+        &module.types[global.ty].inner
+    else {
+        panic!("material uniform is not a struct");
+    };
+
+    members
+        .iter()
+        .map(|member| Member {
+            name: member.name.clone().unwrap_or_default(),
+            ty: wgsl_type(&module.types[member.ty].inner),
+            offset: member.offset,
+        })
+        .collect()
+}
+
+/// Compares a material's uniform with its shader's, within
+/// the limits [`rust_members`] describes.
+fn check_material<M: Material2d + Typed>() {
+    let ShaderRef::Path(path) = M::fragment_shader()
+    else {
+        panic!("expected a fragment shader asset path");
+    };
+    let module = load_shader(path.path());
+
+    let rust = rust_members::<M>();
+    let wgsl = shader_members(&module);
+
+    if rust != wgsl {
+        let describe = |members: &[Member]| {
+            members
+                .iter()
+                .map(|m| {
+                    format!(
+                        "    {:>3}  {}: {}",
+                        m.offset, m.name, m.ty
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        panic!(
+            "uniform layout of {} does not match {:?}\n  rust:\n{}\n  wgsl:\n{}",
+            M::type_info().type_name(),
+            path.path(),
+            describe(&rust),
+            describe(&wgsl),
+        );
+    }
+}
+
+/// A shader that only calls `color_ramp`, to find it in.
+const COLOR_RAMP: &str = r#"
+#import yt_raymarch_2d::sdf color_ramp
+
+@fragment
+fn fragment() -> @location(0) vec4<f32> {
+    return vec4(color_ramp(0u, 0.), 1.);
+}
+"#;
+
+/// The stops of every array `color_ramp` in `sdf.wgsl`
+/// builds, in the order they're written.
+fn color_ramp_stops() -> Vec<[[f32; 3]; 5]> {
+    let mut composer = composer();
+    let module = composer
+        .make_naga_module(NagaModuleDescriptor {
+            source: COLOR_RAMP,
+            file_path: "color_ramp.wgsl",
+            ..default()
+        })
+        .unwrap_or_else(|err| {
+            panic!("{}", err.emit_to_string(&composer))
+        });
+    let (_, function) = module
+        .functions
+        .iter()
+        .find(|(_, function)| {
+            // naga_oil decorates imported names
+            function.name.as_deref().is_some_and(|name| {
+                name.contains("color_ramp")
+            })
+        })
+        .expect("sdf.wgsl should have a color_ramp");
+    let expressions = &function.expressions;
+    let number = |expression| match expressions[expression]
+    {
+        Expression::Constant(constant) => {
+            match module.constants[constant].inner {
+                ConstantInner::Scalar {
+                    value: ScalarValue::Float(value),
+                    ..
+                } => value as f32,
+                ref other => {
+                    panic!(
+                        "expected a float, not {other:?}"
+                    )
+                }
+            }
+        }
+        ref other => {
+            panic!("expected a constant, not {other:?}")
+        }
+    };
+    let color = |expression| match &expressions[expression]
+    {
+        Expression::Compose { components, .. } => {
+            [0, 1, 2].map(|i| number(components[i]))
+        }
+        Expression::Splat { value, .. } => {
+            [number(*value); 3]
+        }
+        other => panic!("expected a vec3, not {other:?}"),
+    };
+    expressions
+        .iter()
+        .filter_map(|(_, expression)| match expression {
+            Expression::Compose { ty, components }
+                if matches!(
+                    module.types[*ty].inner,
+                    TypeInner::Array { .. }
+                ) =>
+            {
+                let stops: Vec<_> = components
+                    .iter()
+                    .map(|component| color(*component))
+                    .collect();
+                Some(
+                    stops
+                        .try_into()
+                        .expect("expected five stops"),
+                )
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn color_ramp_has_the_color_map_stops() {
+    let stops: Vec<_> = ColorMap::NAMES
+        .iter()
+        .filter_map(|name| ColorMap::from_name(name))
+        .filter_map(|map| map.stops().copied())
+        .collect();
+    assert_eq!(color_ramp_stops(), stops);
+}
+
+#[test]
+fn every_asset_validates() {
+    let assets = std::fs::read_dir(manifest_path("assets"))
+        .expect("assets directory should exist");
+    for entry in assets {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "wgsl")
+        {
+            load_shader(&path);
+        }
+    }
+}
+
+#[test]
+fn single_sdf_distance_as_gradient() {
+    check_material::<
+        single_sdf_distance_as_gradient::SdfDemoMaterial,
+    >();
+}
+
+#[test]
+fn single_sdf_distance_as_gradient_with_mouse() {
+    check_material::<
+        single_sdf_distance_as_gradient_with_mouse::SdfDemoMaterial,
+    >();
+}
+
+#[test]
+fn single_sdf_distance_as_gradient_with_mouse_and_inner_ray()
+{
+    check_material::<
+        single_sdf_distance_as_gradient_with_mouse_and_inner_ray::SdfDemoMaterial,
+    >();
+}
+
+#[test]
+fn single_sdf_distance_as_gradient_with_abs_mouse() {
+    check_material::<
+        single_sdf_distance_as_gradient_with_abs_mouse::SdfDemoMaterial,
+    >();
+}
+
+#[test]
+fn single_sdf_distance_as_gradient_with_algorithm() {
+    check_material::<
+        single_sdf_distance_as_gradient_with_algorithm::SdfDemoMaterial,
+    >();
+}
+
+#[test]
+fn single_sdf_distance_as_circle() {
+    check_material::<
+        single_sdf_distance_as_circle::SdfDemoMaterial,
+    >();
+}
+
+#[test]
+fn single_sdf_distance_as_border() {
+    check_material::<
+        single_sdf_distance_as_border::SdfDemoMaterial,
+    >();
+}
+
+#[test]
+fn playground() {
+    check_material::<playground::SdfDemoMaterial>();
+}
+
+#[test]
+fn raymarch_cost() {
+    check_material::<raymarch_cost::SdfDemoMaterial>();
+}
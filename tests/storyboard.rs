@@ -0,0 +1,127 @@
+//! Storyboards have to play out the same way every time.
+
+use bevy::prelude::*;
+use yt_raymarch_2d::{
+    bevy_plugin_shader2d::{SdfHeadlessPlugin, SdfMouse},
+    params::Params,
+    registry,
+    sdf::Shape,
+    storyboard::{
+        Storyboard, StoryboardError, StoryboardPlugin,
+    },
+};
+
+const TAKE: &str = r#"
+Storyboard(
+    example: "single-sdf-distance-as-gradient-with-mouse",
+    keyframes: [
+        (time: 2.0, mouse: Some((1.0, 0.0)), caption: Some("two")),
+        (time: 0.0, mouse: Some((0.0, 0.0)), caption: Some("zero")),
+        (time: 3.0, shape: Some("cool-s"), zoom: Some(3.0)),
+        (time: 4.0, mouse: Some((0.0, 1.0)), ease: Step),
+    ],
+)
+"#;
+
+#[test]
+fn keyframes_are_interpolated() {
+    let storyboard = Storyboard::from_ron(TAKE).unwrap();
+    assert_eq!(storyboard.fps, 60.);
+    assert_eq!(storyboard.duration(), 4.);
+
+    let frame = storyboard.sample(1.);
+    assert_eq!(frame.mouse, Some(Vec2::new(0.5, 0.)));
+    assert_eq!(frame.caption, "zero");
+    assert_eq!(frame.shape, None);
+    // holds the first value until the first keyframe
+    assert_eq!(frame.zoom, Some(3.));
+
+    let frame = storyboard.sample(3.5);
+    assert_eq!(frame.mouse, Some(Vec2::new(1., 0.)));
+    assert_eq!(frame.caption, "two");
+    assert_eq!(frame.shape, Some(Shape::CoolS));
+
+    assert_eq!(
+        storyboard.sample(4.).mouse,
+        Some(Vec2::new(0., 1.))
+    );
+}
+
+/// The mouse, the camera and its scale after `updates`
+/// frames of `storyboard`, played in a new app.
+fn play(
+    storyboard: &Storyboard,
+    updates: u32,
+) -> (SdfMouse, Transform, f32) {
+    let mut app = App::new();
+    app.add_plugins(SdfHeadlessPlugin);
+    // the camera is only moved in a window
+    app.world.spawn(Window::default());
+    let example =
+        registry::find(&storyboard.example).unwrap();
+    (example.add)(&mut app, &Params::defaults(example));
+    app.add_plugins(StoryboardPlugin {
+        storyboard: storyboard.clone(),
+    });
+    for _ in 0..updates {
+        app.update();
+    }
+    let (transform, projection) = app
+        .world
+        .query_filtered::<
+            (&Transform, &OrthographicProjection),
+            With<Camera2d>,
+        >()
+        .single(&app.world);
+    (
+        *app.world.resource::<SdfMouse>(),
+        *transform,
+        projection.scale,
+    )
+}
+
+#[test]
+fn takes_play_the_same_every_time() {
+    let storyboard = Storyboard::from_ron(TAKE).unwrap();
+    // the last update plays the frame 1s in
+    let played = play(&storyboard, 61);
+    assert_eq!(played, play(&storyboard, 61));
+    assert_eq!(
+        played.0,
+        SdfMouse {
+            position: Vec2::new(0.5, 0.),
+            pinned: true,
+        }
+    );
+    assert_eq!(played.2, 1. / 3.);
+}
+
+#[test]
+fn keyframes_are_checked_against_the_example() {
+    let take = TAKE.replace(
+        r#"zoom: Some(3.0)"#,
+        r#"params: {"thickness": "0.02"}"#,
+    );
+    assert!(matches!(
+        Storyboard::from_ron(&take),
+        Err(StoryboardError::Keyframe { time, .. }) if time == 3.
+    ));
+    let take = TAKE.replace(
+        r#"zoom: Some(3.0)"#,
+        r#"params: {"contours": "true"}"#,
+    );
+    assert!(matches!(
+        Storyboard::from_ron(&take),
+        Err(StoryboardError::Fixed { name, .. }) if name == "contours"
+    ));
+    let take =
+        TAKE.replace("keyframes:", "fps: 0.0, keyframes:");
+    assert!(matches!(
+        Storyboard::from_ron(&take),
+        Err(StoryboardError::Fps(_))
+    ));
+    assert!(matches!(
+        Storyboard::from_ron("Storyboard("),
+        Err(StoryboardError::Parse(_))
+    ));
+}
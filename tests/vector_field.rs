@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use yt_raymarch_2d::{
+    sdf::{Sdf, Shape},
+    vector_field::VectorField,
+};
+
+fn field(scale: f32) -> VectorField {
+    VectorField {
+        enabled: true,
+        cells: 12,
+        scale,
+    }
+}
+
+#[test]
+fn arrows_end_on_a_circle() {
+    let arrows = field(1.).arrows(
+        &Shape::Circle,
+        Vec2::splat(-1.),
+        Vec2::splat(1.),
+    );
+    assert_eq!(arrows.len(), 12 * 12);
+    for arrow in arrows {
+        let end = Shape::Circle.distance(arrow.end);
+        assert!(
+            end.abs() < 0.001,
+            "{arrow:?} ends at {end}"
+        );
+    }
+}
+
+#[test]
+fn arrows_point_towards_every_shape() {
+    for shape in Shape::ALL {
+        let arrows = field(0.5).arrows(
+            &shape,
+            Vec2::new(-1.5, -1.),
+            Vec2::new(1.5, 1.),
+        );
+        assert!(!arrows.is_empty());
+        for arrow in arrows {
+            assert!(
+                shape.distance(arrow.end).abs()
+                    < arrow.distance.abs(),
+                "{shape:?}: {arrow:?} points away"
+            );
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! The regions and bisectors of a union of shapes.
+
+use bevy::prelude::*;
+use yt_raymarch_2d::{
+    marching_squares::MarchingSquares,
+    voronoi::{bisectors, closest, region_image},
+};
+
+/// A circle of radius 0.5 around `center`.
+fn circle(center: Vec2) -> impl Fn(Vec2) -> f32 {
+    move |point: Vec2| point.distance(center) - 0.5
+}
+
+#[test]
+fn the_closest_shape_is_found() {
+    let circles =
+        [circle(Vec2::new(-1., 0.)), circle(Vec2::X)];
+    assert_eq!(
+        closest(&circles, Vec2::new(-0.2, 3.)),
+        Some(0)
+    );
+    assert_eq!(
+        closest(&circles, Vec2::new(0.2, -3.)),
+        Some(1)
+    );
+    assert_eq!(closest(&circles[..0], Vec2::ZERO), None);
+}
+
+#[test]
+fn two_circles_are_bisected_halfway() {
+    let circles = [
+        circle(Vec2::new(-0.9, 0.3)),
+        circle(Vec2::new(1.1, 0.3)),
+    ];
+    let polylines = bisectors(
+        &MarchingSquares::default(),
+        &circles,
+        Vec2::splat(-2.),
+        Vec2::splat(2.),
+    );
+    assert_eq!(polylines.len(), 1);
+    let points = &polylines[0];
+    for point in points {
+        assert!((point.x - 0.1).abs() < 1e-4, "{point}");
+    }
+    // from one edge of the region to the other
+    let ys = points.iter().map(|point| point.y);
+    assert!(
+        ys.clone().fold(f32::INFINITY, f32::min) < -1.9
+    );
+    assert!(ys.fold(f32::NEG_INFINITY, f32::max) > 1.9);
+}
+
+#[test]
+fn bisectors_stop_where_a_third_shape_is_closer() {
+    let circles = [
+        circle(Vec2::new(-2., 0.)),
+        circle(Vec2::ZERO),
+        circle(Vec2::new(2., 0.)),
+    ];
+    let polylines = bisectors(
+        &MarchingSquares::default(),
+        &circles,
+        Vec2::splat(-3.),
+        Vec2::splat(3.),
+    );
+    // the outer circles never meet
+    assert_eq!(polylines.len(), 2);
+    for point in polylines.iter().flatten() {
+        assert!(
+            (point.x.abs() - 1.).abs() < 1e-4,
+            "{point}"
+        );
+    }
+}
+
+#[test]
+fn regions_take_the_closest_colour() {
+    let circles =
+        [circle(Vec2::new(-1., 0.)), circle(Vec2::X)];
+    // texels 0.5 across, the first row from y 1 to 0.5
+    let image = region_image(
+        &circles,
+        &[Color::RED, Color::BLUE],
+        Vec2::new(-2., -1.),
+        Vec2::new(2., 1.),
+        UVec2::new(8, 4),
+    );
+    let texel = |x: usize, y: usize| {
+        let start = (y * 8 + x) * 4;
+        image.data[start..start + 4].to_vec()
+    };
+    assert_eq!(texel(2, 1), [255, 0, 0, 255]);
+    assert_eq!(texel(0, 0), [127, 0, 0, 255]);
+    assert_eq!(texel(6, 2), [0, 0, 255, 255]);
+    assert_eq!(texel(7, 3), [0, 0, 127, 255]);
+}